@@ -0,0 +1,129 @@
+//! Counting heap allocations, for finding allocation-heavy generators and solvers.
+//!
+//! Counting is opt-in: a binary installs [`Counter`] as its global allocator, as the
+//! CLI does when built with the `count-allocations` feature. The runner then
+//! [`measure`]s every generator and solver call. Counts are kept per thread, so
+//! calls running on other threads do not skew them.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use serde::Serialize;
+
+/// Allocations made by a call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Allocations {
+    /// Allocations, counting each reallocation as one.
+    pub count: u64,
+    /// Bytes allocated in total, counting a reallocation's new size.
+    pub bytes: u64,
+    /// The most bytes held at once, above what was held before the call.
+    pub peak: u64,
+}
+
+thread_local! {
+    static COUNT: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+    static CURRENT: Cell<u64> = const { Cell::new(0) };
+    static PEAK: Cell<u64> = const { Cell::new(0) };
+}
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// A global allocator which counts the allocations of each thread, then leaves
+/// them to the system allocator.
+pub struct Counter;
+
+impl Counter {
+    fn allocated(size: usize) {
+        // Fails only while the thread is being torn down, when nobody is measuring.
+        let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+        let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + size as u64));
+        let _ = CURRENT.try_with(|current| {
+            current.set(current.get() + size as u64);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+        });
+
+        if !INSTALLED.load(Ordering::Relaxed) {
+            INSTALLED.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn freed(size: usize) {
+        // Memory freed on another thread than it was allocated on would underflow.
+        let _ = CURRENT.try_with(|current| current.set(current.get().saturating_sub(size as u64)));
+    }
+}
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::allocated(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::allocated(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        Self::freed(layout.size());
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::freed(layout.size());
+        Self::allocated(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Whether [`Counter`] is the global allocator.
+pub fn is_counting() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Calls `f`, counting the allocations it makes on this thread. Without [`Counter`]
+/// installed, there are none to count.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    let (count, bytes, current) = (COUNT.get(), BYTES.get(), CURRENT.get());
+    let outer_peak = PEAK.replace(current);
+
+    let result = f();
+
+    let peak = PEAK.get();
+    PEAK.set(outer_peak.max(peak));
+
+    let allocations = Allocations {
+        count: COUNT.get() - count,
+        bytes: BYTES.get() - bytes,
+        peak: peak.saturating_sub(current),
+    };
+
+    (result, allocations)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[global_allocator]
+    static COUNTER: Counter = Counter;
+
+    #[test]
+    fn test_measure() {
+        let (_, outer) = measure(|| {
+            let (kept, inner) = measure(|| vec![0u8; 1000]);
+            assert_eq!((inner.count, inner.bytes, inner.peak), (1, 1000, 1000));
+
+            let _freed = vec![0u8; 500];
+            kept
+        });
+
+        assert_eq!((outer.count, outer.bytes, outer.peak), (2, 1500, 1500));
+        assert!(is_counting());
+    }
+}
@@ -0,0 +1,340 @@
+//! Life-like cellular automata over different topologies.
+//!
+//! A cell is either live or dead. Each generation, a dead cell comes to life if
+//! its number of live neighbors is one of the rule's birth counts, and a live cell
+//! stays alive if it is one of the survival counts.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::Hash,
+    str::FromStr,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    grid::{self, Grid},
+    limits::Checkpoint,
+    Result,
+};
+
+/// Birth and survival neighbor counts, written like `B3/S23`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl Rule {
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        Self {
+            birth: birth.to_vec(),
+            survival: survival.to_vec(),
+        }
+    }
+
+    /// Whether a cell is live in the next generation.
+    pub fn next(&self, live: bool, neighbors: usize) -> bool {
+        if live {
+            self.survival.contains(&neighbors)
+        } else {
+            self.birth.contains(&neighbors)
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let counts = |part: Option<&str>, prefix: char| {
+            part.and_then(|part| part.strip_prefix(prefix))
+                .and_then(|digits| {
+                    digits
+                        .chars()
+                        .map(|c| c.to_digit(10).map(|n| n as usize))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| format!("expected a rule like \"B3/S23\", got {:?}", s))
+        };
+
+        let mut parts = s.split('/');
+        let birth = counts(parts.next(), 'B')?;
+        let survival = counts(parts.next(), 'S')?;
+
+        if parts.next().is_some() {
+            return Err(format!("expected a rule like \"B3/S23\", got {:?}", s));
+        }
+
+        Ok(Self { birth, survival })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B{}/S{}", self.birth.iter().join(""), self.survival.iter().join(""))
+    }
+}
+
+/// Which cells exist and which of them neighbor each other.
+pub trait Topology {
+    type Cell: Clone + Eq + Hash;
+
+    /// The cells whose state counts towards `cell`. Must be symmetric.
+    fn neighbors(&self, cell: &Self::Cell) -> impl Iterator<Item = Self::Cell>;
+
+    /// Every cell, if there are finitely many.
+    ///
+    /// Rules which bring cells without live neighbors to life need this.
+    fn cells(&self) -> Option<impl Iterator<Item = Self::Cell>>;
+}
+
+/// Which cells of a [`Bounded`] grid neighbor each other.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// The up to 8 surrounding cells.
+    Adjacent,
+    /// The first cell seen in each of the 8 directions, skipping gaps.
+    LineOfSight,
+}
+
+/// A rectangular grid in which some positions are gaps instead of cells.
+#[derive(Debug, Clone)]
+pub struct Bounded {
+    cells: Grid<bool>,
+    neighborhood: Neighborhood,
+}
+
+impl Bounded {
+    /// `cells` is true at positions which hold a cell.
+    pub fn new(cells: Grid<bool>, neighborhood: Neighborhood) -> Self {
+        Self { cells, neighborhood }
+    }
+}
+
+impl Topology for Bounded {
+    type Cell = (usize, usize);
+
+    fn neighbors(&self, &cell: &(usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        grid::DIRECTIONS.iter().filter_map(move |&dir| match self.neighborhood {
+            Neighborhood::Adjacent => self.cells.step(cell, dir).filter(|&pos| self.cells[pos]),
+            Neighborhood::LineOfSight => std::iter::successors(Some(cell), |&pos| self.cells.step(pos, dir))
+                .skip(1)
+                .find(|&pos| self.cells[pos]),
+        })
+    }
+
+    fn cells(&self) -> Option<impl Iterator<Item = (usize, usize)>> {
+        Some(self.cells.positions().filter(move |&pos| self.cells[pos]))
+    }
+}
+
+/// An infinite square lattice in any number of dimensions.
+#[derive(Debug, Copy, Clone)]
+pub struct Lattice {
+    pub dimensions: usize,
+}
+
+impl Lattice {
+    pub fn new(dimensions: usize) -> Self {
+        Self { dimensions }
+    }
+
+    /// Embeds a point of fewer dimensions, with zeros for the missing coordinates.
+    pub fn embed(&self, point: &[i32]) -> Vec<i32> {
+        assert!(point.len() <= self.dimensions, "point has too many dimensions");
+
+        let mut embedded = point.to_vec();
+        embedded.resize(self.dimensions, 0);
+        embedded
+    }
+}
+
+impl Topology for Lattice {
+    type Cell = Vec<i32>;
+
+    fn neighbors(&self, cell: &Vec<i32>) -> impl Iterator<Item = Vec<i32>> {
+        let cell = cell.clone();
+
+        cell.iter()
+            .map(|n| (n - 1)..=(n + 1))
+            .multi_cartesian_product()
+            .filter(move |neighbor| *neighbor != cell)
+    }
+
+    fn cells(&self) -> Option<impl Iterator<Item = Vec<i32>>> {
+        None::<std::iter::Empty<_>>
+    }
+}
+
+/// Cube coordinate offsets `(x, y, z)` to the 6 neighbors of a hexagon.
+pub const HEX_DIRECTIONS: [(i64, i64, i64); 6] = [
+    (1, -1, 0),
+    (0, -1, 1),
+    (-1, 0, 1),
+    (-1, 1, 0),
+    (0, 1, -1),
+    (1, 0, -1),
+];
+
+/// An infinite hexagonal lattice in cube coordinates, where `x + y + z == 0`.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Hex;
+
+impl Topology for Hex {
+    type Cell = (i64, i64, i64);
+
+    fn neighbors(&self, &(x, y, z): &(i64, i64, i64)) -> impl Iterator<Item = (i64, i64, i64)> {
+        HEX_DIRECTIONS
+            .iter()
+            .map(move |&(dx, dy, dz)| (x + dx, y + dy, z + dz))
+    }
+
+    fn cells(&self) -> Option<impl Iterator<Item = (i64, i64, i64)>> {
+        None::<std::iter::Empty<_>>
+    }
+}
+
+/// How long to run an [`Automaton`] for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Generations(usize),
+    /// Until a generation changes nothing, which never happens for oscillating patterns.
+    UntilStable,
+}
+
+/// The live cells of a topology under a rule.
+#[derive(Debug, Clone)]
+pub struct Automaton<T: Topology> {
+    topology: T,
+    rule: Rule,
+    live: HashSet<T::Cell>,
+    generation: usize,
+}
+
+impl<T: Topology> Automaton<T> {
+    pub fn new(topology: T, rule: Rule, live: impl IntoIterator<Item = T::Cell>) -> Self {
+        Self {
+            topology,
+            rule,
+            live: live.into_iter().collect(),
+            generation: 0,
+        }
+    }
+
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+
+    pub fn live(&self) -> &HashSet<T::Cell> {
+        &self.live
+    }
+
+    pub fn population(&self) -> usize {
+        self.live.len()
+    }
+
+    /// The number of generations run so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Runs a single generation. Returns whether any cell changed.
+    pub fn step(&mut self) -> bool {
+        let mut num_live: HashMap<T::Cell, usize> = HashMap::new();
+
+        for cell in &self.live {
+            for neighbor in self.topology.neighbors(cell) {
+                *num_live.entry(neighbor).or_default() += 1;
+            }
+        }
+
+        let count = |cell: &T::Cell| num_live.get(cell).copied().unwrap_or_default();
+
+        let next: HashSet<T::Cell> = if self.rule.next(false, 0) {
+            self.topology
+                .cells()
+                .expect("rules with birth on 0 neighbors need a bounded topology")
+                .filter(|cell| self.rule.next(self.live.contains(cell), count(cell)))
+                .collect()
+        } else {
+            let survivors = self
+                .live
+                .iter()
+                .filter(|cell| self.rule.next(true, count(cell)))
+                .cloned();
+
+            let births = num_live
+                .iter()
+                .filter(|&(cell, &n)| !self.live.contains(cell) && self.rule.next(false, n))
+                .map(|(cell, _)| cell.clone());
+
+            survivors.chain(births).collect()
+        };
+
+        let changed = next != self.live;
+
+        self.live = next;
+        self.generation += 1;
+
+        changed
+    }
+
+    /// Returns the number of generations run.
+    pub fn run(&mut self, mode: Mode) -> usize {
+        self.run_traced(mode, |_| ())
+    }
+
+    /// Like [`Automaton::run`], but calls `trace` with the starting state and after
+    /// every generation.
+    pub fn run_traced(&mut self, mode: Mode, trace: impl FnMut(&Self)) -> usize {
+        self.run_checked(mode, &mut Checkpoint::unlimited(), trace)
+            .expect("unlimited checkpoints never fail")
+    }
+
+    /// Like [`Automaton::run_traced`], but ticks `checkpoint` after every generation,
+    /// stopping if the solver runs out of budget.
+    pub fn run_checked(
+        &mut self,
+        mode: Mode,
+        checkpoint: &mut Checkpoint,
+        mut trace: impl FnMut(&Self),
+    ) -> Result<usize> {
+        let start = self.generation;
+
+        trace(self);
+
+        match mode {
+            Mode::Generations(n) => {
+                for _ in 0..n {
+                    self.step();
+                    trace(self);
+                    checkpoint.tick()?;
+                }
+            }
+            Mode::UntilStable => {
+                while self.step() {
+                    trace(self);
+                    checkpoint.tick()?;
+                }
+            }
+        }
+
+        Ok(self.generation - start)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rule_round_trip() {
+        let rule: Rule = "B36/S23".parse().unwrap();
+
+        assert_eq!(rule, Rule::new(&[3, 6], &[2, 3]));
+        assert_eq!(rule.to_string(), "B36/S23");
+        assert!("B3S23".parse::<Rule>().is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Repeatedly times a day's generator and solvers, separately.
+
+use std::{
+    fs,
+    hint::black_box,
+    io::{self, ErrorKind},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{registry::Day, Result};
+
+/// Summary statistics over a set of timing samples.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Self {
+            samples: n,
+            mean: Duration::from_secs_f64(mean),
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// What was timed: the generator, or the solver for a part.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    Generator,
+    Part(u32),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u32,
+    pub target: Target,
+    pub stats: Stats,
+}
+
+/// How many samples to take of each target.
+#[derive(Debug, Copy, Clone)]
+pub struct Config {
+    pub iterations: usize,
+    /// Stop sampling a target early once this much time was spent on it,
+    /// although it is always sampled at least once.
+    pub budget: Duration,
+}
+
+fn sample(config: &Config, mut f: impl FnMut()) -> Stats {
+    let start = Instant::now();
+    let mut samples = Vec::with_capacity(config.iterations);
+
+    while samples.len() < config.iterations.max(1) {
+        let sample = Instant::now();
+        f();
+        samples.push(sample.elapsed());
+
+        if start.elapsed() >= config.budget {
+            break;
+        }
+    }
+
+    Stats::new(&samples)
+}
+
+/// Benchmarks the generator of `day`, then each of `parts` on its output.
+pub fn bench(day: &Day, input: &str, parts: &[u32], config: &Config) -> Result<Vec<Measurement>> {
+    let generated = day.generate(input)?;
+
+    let mut measurements = vec![Measurement {
+        year: day.year,
+        day: day.day,
+        target: Target::Generator,
+        stats: sample(config, || {
+            black_box(day.generate(input).ok());
+        }),
+    }];
+
+    for &part in parts {
+        let solver = day.solver(part).expect("no solver for part");
+
+        measurements.push(Measurement {
+            year: day.year,
+            day: day.day,
+            target: Target::Part(part),
+            stats: sample(config, || {
+                black_box(solver(generated.as_ref()).ok());
+            }),
+        });
+    }
+
+    Ok(measurements)
+}
+
+/// A measurement as saved to a baseline file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    #[serde(default = "crate::ledger::first_year")]
+    pub year: u16,
+    pub day: u32,
+    pub target: Target,
+    pub samples: usize,
+    pub mean_secs: f64,
+    pub median_secs: f64,
+    pub stddev_secs: f64,
+}
+
+impl From<&Measurement> for Record {
+    fn from(measurement: &Measurement) -> Self {
+        Record {
+            year: measurement.year,
+            day: measurement.day,
+            target: measurement.target,
+            samples: measurement.stats.samples,
+            mean_secs: measurement.stats.mean.as_secs_f64(),
+            median_secs: measurement.stats.median.as_secs_f64(),
+            stddev_secs: measurement.stats.stddev.as_secs_f64(),
+        }
+    }
+}
+
+/// Previously saved measurements to compare against.
+#[derive(Debug, Default)]
+pub struct Baseline(Vec<Record>);
+
+impl Baseline {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+
+        serde_json::from_str(&contents)
+            .map(Baseline)
+            .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+    }
+
+    pub fn save(measurements: &[Measurement], path: &Path) -> io::Result<()> {
+        let records: Vec<Record> = measurements.iter().map(Record::from).collect();
+
+        let mut json = serde_json::to_string_pretty(&records)?;
+        json.push('\n');
+
+        fs::write(path, json)
+    }
+
+    /// The relative change in median time from the baseline, e.g. `0.25` for 25% slower.
+    pub fn change(&self, measurement: &Measurement) -> Option<f64> {
+        let record = self
+            .0
+            .iter()
+            .find(|record| {
+                let key = (record.year, record.day, record.target);
+                key == (measurement.year, measurement.day, measurement.target)
+            })?;
+
+        if record.median_secs == 0.0 {
+            return None;
+        }
+
+        Some(measurement.stats.median.as_secs_f64() / record.median_secs - 1.0)
+    }
+}
@@ -0,0 +1,296 @@
+//! Answers puzzles over HTTP, on localhost only.
+//!
+//! `POST /{year}/day/{n}/part/{p}` with the puzzle input as the body returns the
+//! answer and timings as JSON, shaped like the CLI's `--format json` records.
+//!
+//! Each request is solved in a child process (this binary again, with `--worker`)
+//! so that a solver which runs past the time limit can be killed, and one which
+//! allocates past the memory limit fails alone. Requests are solved one at a time,
+//! so the memory limit also bounds the whole service.
+
+use std::{
+    convert::TryFrom,
+    io::{self, Read, Write},
+    process::{self, Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use advent_of_code_2020::{
+    limits::Limits,
+    registry::{self, Day},
+    runner, Error,
+};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+#[derive(Parser)]
+#[command(about = "Answers Advent of Code puzzles over HTTP, on localhost")]
+struct Cli {
+    #[arg(long, default_value_t = 8020)]
+    port: u16,
+
+    /// Seconds a request may take before its solver is killed
+    #[arg(long, default_value_t = 10)]
+    time_limit: u64,
+
+    /// Megabytes of memory a request's solver may use (only enforced on Unix)
+    #[arg(long, default_value_t = 1024)]
+    memory_limit: u64,
+
+    /// Largest accepted input, in kilobytes
+    #[arg(long, default_value_t = 1024)]
+    max_input: u64,
+
+    /// Solve one part with the input on stdin, replying on stdout
+    #[arg(long, hide = true, num_args = 3, value_names = ["YEAR", "DAY", "PART"])]
+    worker: Option<Vec<u32>>,
+}
+
+/// The answer to one part, or why there is none.
+#[derive(Debug, Serialize, Deserialize)]
+struct Reply {
+    year: u16,
+    day: u32,
+    part: u32,
+    /// "ok", "invalid input", "gave up", "panicked", "timed out" or "out of memory".
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    generator_secs: f64,
+    solver_secs: f64,
+}
+
+impl Reply {
+    fn failed(day: &Day, part: u32, status: &str, error: String) -> Self {
+        Reply {
+            year: day.year,
+            day: day.day,
+            part,
+            status: status.to_string(),
+            answer: None,
+            error: Some(error),
+            generator_secs: 0.0,
+            solver_secs: 0.0,
+        }
+    }
+
+    fn http_status(&self) -> u16 {
+        match self.status.as_str() {
+            "ok" => 200,
+            "invalid input" | "gave up" => 422,
+            "timed out" | "out of memory" => 503,
+            _ => 500,
+        }
+    }
+}
+
+/// Runs in the child process.
+fn worker(day: &Day, part: u32) {
+    let mut input = String::new();
+
+    if let Err(err) = io::stdin().read_to_string(&mut input) {
+        eprintln!("cannot read input: {}", err);
+        process::exit(2);
+    }
+
+    let outcome = runner::run(day, &input, &[part], &Limits::default()).remove(0);
+
+    let (status, answer, error) = match outcome.answer {
+        Ok(answer) => ("ok", Some(answer), None),
+        Err(err @ Error::Panicked { .. }) => ("panicked", None, Some(err.to_string())),
+        Err(err @ Error::GaveUp { .. }) => ("gave up", None, Some(err.to_string())),
+        Err(err) => ("invalid input", None, Some(err.to_string())),
+    };
+
+    let reply = Reply {
+        year: outcome.year,
+        day: outcome.day,
+        part,
+        status: status.to_string(),
+        answer,
+        error,
+        generator_secs: outcome.generator.as_secs_f64(),
+        solver_secs: outcome.solver.as_secs_f64(),
+    };
+
+    println!("{}", serde_json::to_string(&reply).unwrap());
+}
+
+#[cfg(unix)]
+fn limit_memory(command: &mut Command, megabytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let bytes = megabytes.saturating_mul(1 << 20) as libc::rlim_t;
+
+    // Only calls `setrlimit`, which is async-signal-safe, between fork and exec.
+    unsafe {
+        command.pre_exec(move || {
+            let limit = libc::rlimit {
+                rlim_cur: bytes,
+                rlim_max: bytes,
+            };
+
+            if libc::setrlimit(libc::RLIMIT_AS, &limit) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_command: &mut Command, _megabytes: u64) {}
+
+/// Waits for `child` until `deadline`, killing it if it is still running then.
+/// Returns whether it exited in time.
+fn wait_until(child: &mut Child, deadline: Instant) -> io::Result<bool> {
+    loop {
+        if child.try_wait()?.is_some() {
+            return Ok(true);
+        }
+
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Ok(false);
+        }
+
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Solves `part` of `day` in a child process, within the limits.
+fn solve(cli: &Cli, day: &Day, part: u32, input: &str) -> io::Result<Reply> {
+    let mut command = Command::new(std::env::current_exe()?);
+
+    command
+        .arg("--worker")
+        .args([day.year.to_string(), day.day.to_string(), part.to_string()])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    limit_memory(&mut command, cli.memory_limit);
+
+    let deadline = Instant::now() + Duration::from_secs(cli.time_limit);
+    let mut child = command.spawn()?;
+
+    // The worker reads all of its input before doing anything, so this cannot block
+    // on a full stdout. It fails if the worker already died, which the reply tells.
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+
+    if !wait_until(&mut child, deadline)? {
+        let error = format!("no answer within the time limit of {}s", cli.time_limit);
+        return Ok(Reply::failed(day, part, "timed out", error));
+    }
+
+    let (mut stdout, mut stderr) = (String::new(), String::new());
+    child.stdout.take().unwrap().read_to_string(&mut stdout)?;
+    child.stderr.take().unwrap().read_to_string(&mut stderr)?;
+
+    if let Ok(reply) = serde_json::from_str(&stdout) {
+        return Ok(reply);
+    }
+
+    Ok(if stderr.contains("memory allocation of") {
+        let error = format!("exceeded the memory limit of {} MB", cli.memory_limit);
+        Reply::failed(day, part, "out of memory", error)
+    } else {
+        let error = format!("worker failed: {}", stderr.trim());
+        Reply::failed(day, part, "panicked", error)
+    })
+}
+
+/// Parses `/{year}/day/{n}/part/{p}`.
+fn route(url: &str) -> Option<(u16, u32, u32)> {
+    let path = url.split('?').next()?.trim_matches('/');
+
+    match path.split('/').collect::<Vec<_>>().as_slice() {
+        [year, "day", day, "part", part] => {
+            Some((year.parse().ok()?, day.parse().ok()?, part.parse().ok()?))
+        }
+        _ => None,
+    }
+}
+
+fn respond(request: Request, status: u16, body: &impl Serialize) {
+    let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+    let response = Response::from_string(serde_json::to_string(body).unwrap())
+        .with_status_code(status)
+        .with_header(header);
+
+    if let Err(err) = request.respond(response) {
+        eprintln!("cannot respond: {}", err);
+    }
+}
+
+fn respond_error(request: Request, status: u16, error: impl ToString) {
+    respond(request, status, &serde_json::json!({ "error": error.to_string() }));
+}
+
+fn handle(cli: &Cli, mut request: Request) {
+    let (year, day, part) = match route(request.url()) {
+        Some(route) => route,
+        None => return respond_error(request, 404, "expected /{year}/day/{n}/part/{p}"),
+    };
+
+    let day = match registry::year(year).and_then(|year| year.day(day)) {
+        Some(day) if day.solver(part).is_some() => day,
+        _ => {
+            let error = format!("no solution for {} day {} part {}", year, day, part);
+            return respond_error(request, 404, error);
+        }
+    };
+
+    if *request.method() != Method::Post {
+        return respond_error(request, 405, "expected POST with the puzzle input as the body");
+    }
+
+    let limit = cli.max_input.saturating_mul(1024);
+    let mut input = String::new();
+
+    let read = request.as_reader().take(limit + 1).read_to_string(&mut input);
+
+    if let Err(err) = read {
+        return respond_error(request, 400, format!("cannot read input: {}", err));
+    }
+
+    if input.len() as u64 > limit {
+        return respond_error(request, 413, format!("input larger than {} KB", cli.max_input));
+    }
+
+    match solve(cli, day, part, &input) {
+        Ok(reply) => respond(request, reply.http_status(), &reply),
+        Err(err) => respond_error(request, 500, format!("cannot run solver: {}", err)),
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if let Some(worker_args) = &cli.worker {
+        let day = u16::try_from(worker_args[0])
+            .ok()
+            .and_then(registry::year)
+            .and_then(|year| year.day(worker_args[1]))
+            .expect("no solution for day");
+
+        return worker(day, worker_args[2]);
+    }
+
+    let server = Server::http(("127.0.0.1", cli.port)).unwrap_or_else(|err| {
+        eprintln!("cannot listen on port {}: {}", cli.port, err);
+        process::exit(1);
+    });
+
+    eprintln!("listening on http://127.0.0.1:{}", cli.port);
+
+    for request in server.incoming_requests() {
+        handle(&cli, request);
+    }
+}
@@ -0,0 +1,113 @@
+//! Differential testing of solvers which rely on a trick, e.g. day 5 finding the
+//! missing seat by XOR, against a straightforward reference implementation.
+//!
+//! Both solve the same [synthesized](crate::synth) inputs, so a divergence can be
+//! reproduced from its seed and size with `synth`. References may be too slow for
+//! large inputs; under a time limit, inputs on which either side gives up are skipped.
+
+use std::ops::Range;
+
+use crate::{
+    limits::Limits,
+    registry::{Day, Solver},
+    runner, Error, Result,
+};
+
+/// One input on which a solver and its reference disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub year: u16,
+    pub day: u32,
+    pub part: u32,
+    pub seed: u64,
+    pub size: usize,
+    pub solver: Result<String>,
+    pub reference: Result<String>,
+}
+
+/// The parts of `day` which have a reference implementation.
+pub fn parts(day: &Day) -> impl Iterator<Item = u32> + '_ {
+    day.parts().filter(move |&part| day.reference(part).is_some())
+}
+
+/// How a solver fared against its reference on one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// Both gave the same answer, or failed the same way.
+    Agreed,
+    /// One of them gave up, e.g. on exceeding the time limit.
+    Skipped,
+    Diverged(Divergence),
+}
+
+/// The verdicts on a batch of inputs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub checked: usize,
+    pub skipped: usize,
+    pub divergences: Vec<Divergence>,
+}
+
+/// Solves the input synthesized from `seed` and `size` with both the solver for
+/// `part` and its reference, each under `limits`.
+pub fn check(day: &Day, part: u32, seed: u64, size: usize, limits: &Limits) -> Verdict {
+    let solver = day.solver(part).expect("no solver for part");
+    let reference = day.reference(part).expect("no reference for part");
+
+    let input = day.synthesize(seed, size).input;
+    let generated = runner::catch(day, limits, || day.generate(&input));
+
+    let answer = |solve: Solver| {
+        generated
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|input| runner::catch(day, limits, || solve(input.as_ref())))
+    };
+
+    match (answer(solver), answer(reference)) {
+        (solver, reference) if solver == reference => Verdict::Agreed,
+        (Err(Error::GaveUp { .. }), _) | (_, Err(Error::GaveUp { .. })) => Verdict::Skipped,
+        (solver, reference) => Verdict::Diverged(Divergence {
+            year: day.year,
+            day: day.day,
+            part,
+            seed,
+            size,
+            solver,
+            reference,
+        }),
+    }
+}
+
+/// Checks every part of `day` with a reference, on the inputs from each of `seeds`.
+pub fn check_all(day: &Day, seeds: Range<u64>, size: usize, limits: &Limits) -> Report {
+    let mut report = Report::default();
+
+    for part in parts(day) {
+        for seed in seeds.clone() {
+            report.checked += 1;
+
+            match check(day, part, seed, size, limits) {
+                Verdict::Agreed => (),
+                Verdict::Skipped => report.skipped += 1,
+                Verdict::Diverged(divergence) => report.divergences.push(divergence),
+            }
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_references_agree() {
+        for day in registry::days() {
+            let report = check_all(day, 0..10, 10, &Limits::default());
+            assert_eq!(report.divergences, vec![], "day {}", day.day);
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! The error type shared by every generator and fallible solver.
+
+use std::{any, fmt, str::FromStr};
+
+use pest::{
+    error::{ErrorVariant, LineColLocation},
+    RuleType,
+};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// `text`, found at `line`:`column` (both 1-based) of the input, is malformed.
+    Parse {
+        day: u32,
+        line: usize,
+        column: usize,
+        text: String,
+        reason: String,
+    },
+    /// The input ended at `line` while `expected` was still missing.
+    UnexpectedEnd {
+        day: u32,
+        line: usize,
+        expected: String,
+    },
+    /// A generator or solver panicked, e.g. on input it assumed could not happen.
+    Panicked { day: u32, message: String },
+    /// A solver stopped without an answer: it ran out of budget, was cancelled, or
+    /// found that there is none.
+    GaveUp { day: u32, reason: String },
+}
+
+impl Error {
+    pub fn parse(day: u32, line: usize, column: usize, text: &str, reason: impl fmt::Display) -> Self {
+        Error::Parse {
+            day,
+            line,
+            column,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn unexpected_end(day: u32, line: usize, expected: impl fmt::Display) -> Self {
+        Error::UnexpectedEnd {
+            day,
+            line,
+            expected: expected.to_string(),
+        }
+    }
+
+    pub fn panicked(day: u32, message: impl fmt::Display) -> Self {
+        Error::Panicked {
+            day,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn gave_up(day: u32, reason: impl fmt::Display) -> Self {
+        Error::GaveUp {
+            day,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Converts a pest error for input which starts at line `first_line`.
+    pub fn from_pest<R: RuleType>(day: u32, first_line: usize, input: &str, error: pest::error::Error<R>) -> Self {
+        let (line, column) = match error.line_col {
+            LineColLocation::Pos(pos) => pos,
+            LineColLocation::Span(start, _) => start,
+        };
+
+        let text: String = input
+            .lines()
+            .nth(line - 1)
+            .unwrap_or_default()
+            .chars()
+            .skip(column - 1)
+            .collect();
+
+        let reason = match error.variant {
+            ErrorVariant::ParsingError { positives, .. } if !positives.is_empty() => {
+                format!("expected one of {:?}", positives)
+            }
+            ErrorVariant::ParsingError { .. } => "unexpected input".to_string(),
+            ErrorVariant::CustomError { message } => message,
+        };
+
+        Error::parse(day, first_line + line - 1, column, &text, reason)
+    }
+
+    /// Converts a serde_json error for `json`, an input in the form `parse --emit json`
+    /// prints.
+    pub fn from_json(day: u32, json: &str, error: serde_json::Error) -> Self {
+        let (line, column) = (error.line().max(1), error.column().max(1));
+
+        let text: String = json
+            .lines()
+            .nth(line - 1)
+            .unwrap_or_default()
+            .chars()
+            .skip(column - 1)
+            .collect();
+
+        let reason = error.to_string();
+        let suffix = format!(" at line {} column {}", error.line(), error.column());
+        let reason = reason.strip_suffix(&suffix).unwrap_or(&reason);
+
+        Error::parse(day, line, column, &text, reason)
+    }
+
+    pub fn day(&self) -> u32 {
+        match *self {
+            Error::Parse { day, .. }
+            | Error::UnexpectedEnd { day, .. }
+            | Error::Panicked { day, .. }
+            | Error::GaveUp { day, .. } => day,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse {
+                day,
+                line,
+                column,
+                text,
+                reason,
+            } => write!(f, "day {}, line {}, column {}: {}: {:?}", day, line, column, reason, text),
+            Error::UnexpectedEnd {
+                day,
+                line,
+                expected,
+            } => write!(f, "day {}, line {}: unexpected end of input, expected {}", day, line, expected),
+            Error::Panicked { day, message } => write!(f, "day {}: panicked: {}", day, message),
+            Error::GaveUp { day, reason } => write!(f, "day {}: gave up: {}", day, reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A line of input, remembering where it came from for error reporting.
+#[derive(Debug, Copy, Clone)]
+pub struct Line<'a> {
+    pub day: u32,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// An error pointing at `token`, which must be a slice of this line.
+    pub fn error(&self, token: &str, reason: impl fmt::Display) -> Error {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(self.text.as_ptr() as usize)
+            .filter(|&offset| offset <= self.text.len())
+            .unwrap_or(0);
+
+        let column = self.text[..offset].chars().count() + 1;
+
+        Error::parse(self.day, self.number, column, token, reason)
+    }
+
+    /// Parses the whole line.
+    pub fn parse<T>(&self) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.parse_token(self.text)
+    }
+
+    /// Parses `token`, which must be a slice of this line.
+    pub fn parse_token<T>(&self, token: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        token.parse().map_err(|err| {
+            let name = any::type_name::<T>().rsplit("::").next().unwrap_or_default();
+            self.error(token, format!("invalid {} ({})", name, err))
+        })
+    }
+
+    /// Parses the whole line, reporting `format` as the expected format on failure.
+    pub fn parse_format<T: FromStr>(&self, format: &str) -> Result<T> {
+        self.text
+            .parse()
+            .map_err(|_| self.error(self.text, format!("expected format {:?}", format)))
+    }
+
+    /// Maps every character of the line with `f`, failing on the first one it rejects.
+    pub fn map_chars<T>(&self, expected: &str, f: impl Fn(char) -> Option<T>) -> Result<Vec<T>> {
+        self.text
+            .char_indices()
+            .map(|(i, c)| {
+                f(c).ok_or_else(|| {
+                    self.error(&self.text[i..i + c.len_utf8()], format!("expected {}", expected))
+                })
+            })
+            .collect()
+    }
+}
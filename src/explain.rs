@@ -0,0 +1,71 @@
+//! Notes solvers leave about how they reached their answers, e.g. which two entries
+//! day 1 found, for comparing against another solution's intermediate results.
+//!
+//! Solvers [`note`] what they find. Notes are only formatted and kept while someone
+//! [`collect`]s them, as the runner does around every solver call.
+
+use std::{cell::RefCell, fmt};
+
+use serde::Serialize;
+
+/// An intermediate result, e.g. `entries` and `1721 + 299 = 2020`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Note {
+    pub day: u32,
+    pub topic: String,
+    pub detail: String,
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.topic, self.detail)
+    }
+}
+
+thread_local! {
+    static NOTES: RefCell<Option<Vec<Note>>> = const { RefCell::new(None) };
+}
+
+/// Whether notes are being collected on this thread, for solvers which need extra
+/// work to explain themselves.
+pub fn is_enabled() -> bool {
+    NOTES.with(|notes| notes.borrow().is_some())
+}
+
+/// Notes `detail` about `topic`, if notes are being collected.
+pub fn note<D: fmt::Display>(day: u32, topic: &str, detail: impl FnOnce() -> D) {
+    NOTES.with(|notes| {
+        if let Some(notes) = notes.borrow_mut().as_mut() {
+            notes.push(Note {
+                day,
+                topic: topic.to_string(),
+                detail: detail().to_string(),
+            });
+        }
+    })
+}
+
+/// Calls `f`, collecting the notes it leaves on this thread.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<Note>) {
+    let previous = NOTES.with(|notes| notes.replace(Some(Vec::new())));
+    let result = f();
+    let notes = NOTES.with(|notes| notes.replace(previous)).unwrap_or_default();
+
+    (result, notes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_collect() {
+        note(1, "ignored", || "not collecting");
+
+        let (_, notes) = collect(|| crate::y2020::day1::part1(&[1721, 979, 366, 299, 675, 1456]));
+
+        let notes: Vec<_> = notes.iter().map(Note::to_string).collect();
+        assert_eq!(notes, vec!["entries: 1721 + 299 = 2020"]);
+        assert!(!is_enabled());
+    }
+}
@@ -0,0 +1,217 @@
+//! Records the states of a simulation as it runs, and exports them as animations.
+
+use std::{
+    convert::TryFrom,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::grid::Grid;
+
+/// A rendered state, as brightness from 0 (background) to 255.
+pub type Frame = Grid<u8>;
+
+/// Collects frames from a simulation. A disabled recorder never renders a frame.
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    enabled: bool,
+    every: usize,
+    limit: usize,
+    offered: usize,
+    frames: Vec<Frame>,
+}
+
+impl Recorder {
+    /// Keeps every `every`th frame offered, up to `limit` frames.
+    pub fn new(every: usize, limit: usize) -> Self {
+        Self {
+            enabled: true,
+            every: every.max(1),
+            limit,
+            ..Self::default()
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Whether another frame would be kept if offered.
+    pub fn wants(&self) -> bool {
+        self.enabled && self.offered.is_multiple_of(self.every) && self.frames.len() < self.limit
+    }
+
+    /// Offers a frame, only calling `render` if it is kept.
+    pub fn record(&mut self, render: impl FnOnce() -> Frame) {
+        if self.wants() {
+            self.frames.push(render());
+        }
+
+        self.offered += 1;
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+}
+
+/// Renders a set of points to a frame just large enough to hold them all.
+pub fn render_points(points: impl IntoIterator<Item = ((i64, i64), u8)>) -> Frame {
+    let points: Vec<_> = points.into_iter().collect();
+
+    let (min_x, max_x) = bounds(points.iter().map(|&((x, _), _)| x));
+    let (min_y, max_y) = bounds(points.iter().map(|&((_, y), _)| y));
+
+    let mut frame = Grid::filled((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize, 0);
+
+    for ((x, y), value) in points {
+        frame[((x - min_x) as usize, (y - min_y) as usize)] = value;
+    }
+
+    frame
+}
+
+fn bounds(values: impl Iterator<Item = i64>) -> (i64, i64) {
+    values.fold(None, |bounds, v| match bounds {
+        Some((min, max)) => Some((v.min(min), v.max(max))),
+        None => Some((v, v)),
+    })
+    .unwrap_or((0, 0))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// Every frame in one text file, drawn with a brightness ramp.
+    Ascii,
+    /// A directory of grayscale images.
+    Pgm,
+    /// A directory of color images.
+    Ppm,
+    /// An animated GIF.
+    Gif,
+}
+
+impl Format {
+    /// The extension of the exported file, or `None` for a directory of images.
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            Format::Ascii => Some("txt"),
+            Format::Gif => Some("gif"),
+            Format::Pgm | Format::Ppm => None,
+        }
+    }
+}
+
+const ASCII_RAMP: &[u8] = b" .:-=+*#%@";
+
+/// Maps brightness onto black, through red and yellow, to white.
+fn heat(value: u8) -> [u8; 3] {
+    let v = value as u16 * 3;
+
+    [v.min(255) as u8, v.saturating_sub(255).min(255) as u8, v.saturating_sub(510) as u8]
+}
+
+/// Pads every frame to the size of the largest, scaling each pixel up `scale` times.
+fn canvas(frames: &[Frame], scale: usize) -> Vec<Frame> {
+    let width = frames.iter().map(Grid::width).max().unwrap_or_default();
+    let height = frames.iter().map(Grid::height).max().unwrap_or_default();
+    let scale = scale.max(1);
+
+    frames
+        .iter()
+        .map(|frame| {
+            let mut canvas = Grid::filled(width * scale, height * scale, 0);
+
+            for pos in canvas.positions() {
+                if let Some(&value) = frame.get((pos.0 / scale, pos.1 / scale)) {
+                    canvas[pos] = value;
+                }
+            }
+
+            canvas
+        })
+        .collect()
+}
+
+/// Writes `frames` to `path`, which is a directory for image sequences.
+pub fn export(frames: &[Frame], format: Format, path: &Path, scale: usize) -> io::Result<()> {
+    match format {
+        Format::Ascii => {
+            let mut out = BufWriter::new(File::create(path)?);
+
+            for (i, frame) in frames.iter().enumerate() {
+                writeln!(out, "frame {}", i)?;
+
+                for row in frame.rows() {
+                    let line: Vec<u8> = row
+                        .iter()
+                        .map(|&v| ASCII_RAMP[v as usize * (ASCII_RAMP.len() - 1) / 255])
+                        .collect();
+
+                    out.write_all(&line)?;
+                    writeln!(out)?;
+                }
+
+                writeln!(out)?;
+            }
+
+            out.flush()
+        }
+        Format::Pgm | Format::Ppm => {
+            fs::create_dir_all(path)?;
+
+            for (i, frame) in canvas(frames, scale).iter().enumerate() {
+                let (magic, extension) = if format == Format::Pgm { ("P5", "pgm") } else { ("P6", "ppm") };
+                let mut out = BufWriter::new(File::create(path.join(format!("frame-{:04}.{}", i, extension)))?);
+
+                write!(out, "{}\n{} {}\n255\n", magic, frame.width(), frame.height())?;
+
+                for &value in frame.cells() {
+                    if format == Format::Pgm {
+                        out.write_all(&[value])?;
+                    } else {
+                        out.write_all(&heat(value))?;
+                    }
+                }
+
+                out.flush()?;
+            }
+
+            Ok(())
+        }
+        Format::Gif => {
+            let frames = canvas(frames, scale);
+            let (width, height) = frames
+                .first()
+                .map(|frame| (frame.width(), frame.height()))
+                .unwrap_or_default();
+
+            let size = |n: usize| u16::try_from(n).map_err(|_| io::Error::other("frames too large for a GIF"));
+            let palette: Vec<u8> = (0..=255u8).flat_map(heat).collect();
+
+            let mut encoder = gif::Encoder::new(BufWriter::new(File::create(path)?), size(width)?, size(height)?, &palette)
+                .map_err(io::Error::other)?;
+
+            encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+
+            for frame in &frames {
+                let mut gif_frame = gif::Frame::from_indexed_pixels(
+                    size(width)?,
+                    size(height)?,
+                    frame.cells().copied().collect::<Vec<_>>(),
+                    None,
+                );
+                gif_frame.delay = 10;
+
+                encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+            }
+
+            Ok(())
+        }
+    }
+}
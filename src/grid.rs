@@ -0,0 +1,251 @@
+//! A rectangular grid of cells, stored row by row.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Offsets to the 8 cells surrounding a cell, as `(dx, dy)`.
+pub const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Cells are addressed by `(x, y)`, with `(0, 0)` at the top left.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells, row by row.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "wrong number of cells for grid size");
+
+        Self { cells, width, height }
+    }
+
+    pub fn filled(width: usize, height: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![cell; width * height])
+    }
+
+    /// Builds a grid from its rows, which must all be the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Self {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for row in rows {
+            assert_eq!(*width.get_or_insert(row.len()), row.len(), "rows of different lengths");
+            cells.extend(row);
+            height += 1;
+        }
+
+        Self::new(width.unwrap_or_default(), height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Looks up a cell as if the grid repeated infinitely in every direction.
+    pub fn get_wrapping(&self, (x, y): (isize, isize)) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+
+        &self[(x, y)]
+    }
+
+    /// The position one step from `(x, y)` by `(dx, dy)`, if it is in the grid.
+    pub fn step(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+
+        Some((x, y))
+    }
+
+    /// The positions of the up to 8 cells surrounding `pos`.
+    pub fn neighbors(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DIRECTIONS.iter().filter_map(move |&dir| self.step(pos, dir))
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column out of bounds");
+
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotated(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| (x, y)))
+            .map(|pos| self[pos].clone())
+            .collect();
+
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Mirrors the grid horizontally.
+    pub fn flipped(&self) -> Self {
+        let cells = self
+            .rows()
+            .flat_map(|row| row.iter().rev().cloned())
+            .collect();
+
+        Self::new(self.width, self.height, cells)
+    }
+
+    /// One of the 8 orientations of the grid: `orientation % 4` quarter turns clockwise,
+    /// after mirroring if `orientation >= 4`.
+    pub fn oriented(&self, orientation: u8) -> Self {
+        let mut grid = if orientation >= 4 {
+            self.flipped()
+        } else {
+            self.clone()
+        };
+
+        for _ in 0..(orientation % 4) {
+            grid = grid.rotated();
+        }
+
+        grid
+    }
+
+    /// All 8 orientations, in the order of [`Grid::oriented`].
+    pub fn orientations(&self) -> impl Iterator<Item = Self> + '_ {
+        (0..8).map(move |orientation| self.oriented(orientation))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        self.get(pos).expect("position out of bounds")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        self.get_mut(pos).expect("position out of bounds")
+    }
+}
+
+/// Serializes as a list of rows.
+impl<T: Serialize> Serialize for Grid<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.rows())
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Grid<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rows = Vec::<Vec<T>>::deserialize(deserializer)?;
+
+        if rows.iter().any(|row| row.len() != rows[0].len()) {
+            return Err(de::Error::custom("rows of different lengths"));
+        }
+
+        Ok(Self::from_rows(rows))
+    }
+}
+
+/// Prints one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rotate() {
+        let before = Grid::from_rows(vec![vec!['1', '2', '3'], vec!['4', '5', '6']]);
+        let expected = Grid::from_rows(vec![vec!['4', '1'], vec!['5', '2'], vec!['6', '3']]);
+
+        assert_eq!(before.rotated(), expected);
+    }
+
+    #[test]
+    fn test_serde() {
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]);
+        let json = serde_json::to_string(&grid).unwrap();
+
+        assert_eq!(json, "[[1,2],[3,4]]");
+        assert_eq!(serde_json::from_str::<Grid<i32>>(&json).unwrap(), grid);
+        assert!(serde_json::from_str::<Grid<i32>>("[[1,2],[3]]").is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Puzzle input, normalized so that how it was saved does not change how it parses.
+
+use std::borrow::Cow;
+
+use crate::{
+    error::{Error, Line, Result},
+    grid::Grid,
+};
+
+pub(crate) const BOM: char = '\u{feff}';
+
+/// Drops a leading byte order mark, converts CRLF line endings to LF and trims
+/// trailing whitespace from every line, including trailing blank lines.
+///
+/// Input which is already normalized is borrowed as is.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix(BOM).unwrap_or(input).trim_end();
+
+    if input.split('\n').any(|line| line.ends_with(char::is_whitespace)) {
+        Cow::Owned(input.lines().map(str::trim_end).collect::<Vec<_>>().join("\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// A day's normalized input.
+#[derive(Debug, Clone)]
+pub struct Input<'a> {
+    day: u32,
+    text: Cow<'a, str>,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(day: u32, input: &'a str) -> Self {
+        Self {
+            day,
+            text: normalize(input),
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The whole input, as a section starting at line 1.
+    pub fn section(&self) -> Section<'_> {
+        Section {
+            day: self.day,
+            first_line: 1,
+            text: &self.text,
+        }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Line<'_>> {
+        self.section().lines()
+    }
+
+    pub fn sections(&self) -> impl Iterator<Item = Section<'_>> {
+        self.section().sections()
+    }
+
+    pub fn grid<T>(&self, expected: &str, f: impl Fn(char) -> Option<T>) -> Result<Grid<T>> {
+        self.section().grid(expected, f)
+    }
+}
+
+/// Part of an [`Input`], remembering which line it starts on for error reporting.
+#[derive(Debug, Copy, Clone)]
+pub struct Section<'a> {
+    day: u32,
+    first_line: usize,
+    text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    pub fn first_line(self) -> usize {
+        self.first_line
+    }
+
+    /// The number of the last line, for reporting input which ends too early.
+    pub fn last_line(self) -> usize {
+        self.first_line + self.text.lines().count().saturating_sub(1)
+    }
+
+    pub fn text(self) -> &'a str {
+        self.text
+    }
+
+    pub fn lines(self) -> impl Iterator<Item = Line<'a>> {
+        let day = self.day;
+
+        self.text
+            .lines()
+            .zip(self.first_line..)
+            .map(move |(text, number)| Line { day, number, text })
+    }
+
+    /// Splits off the first line, e.g. a header, from the lines after it.
+    pub fn split_first_line(self) -> Option<(Line<'a>, Section<'a>)> {
+        let first = self.lines().next()?;
+        let rest = self.text.split_once('\n').map_or("", |(_, rest)| rest);
+
+        Some((first, Section { first_line: self.first_line + 1, text: rest, ..self }))
+    }
+
+    /// Splits the section on runs of blank lines.
+    pub fn sections(self) -> impl Iterator<Item = Section<'a>> {
+        let text = self.text;
+        let mut lines = text.split('\n').zip(self.first_line..).peekable();
+
+        std::iter::from_fn(move || {
+            while lines.next_if(|(line, _)| line.is_empty()).is_some() {}
+
+            let &(first, first_line) = lines.peek()?;
+            let start = first.as_ptr() as usize - text.as_ptr() as usize;
+            let mut end = start;
+
+            while let Some((line, _)) = lines.next_if(|(line, _)| !line.is_empty()) {
+                end = line.as_ptr() as usize - text.as_ptr() as usize + line.len();
+            }
+
+            Some(Section { first_line, text: &text[start..end], ..self })
+        })
+    }
+
+    /// Parses a grid with one line per row, mapping each character to a cell with `f`.
+    pub fn grid<T>(self, expected: &str, f: impl Fn(char) -> Option<T>) -> Result<Grid<T>> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for line in self.lines() {
+            let row = line.map_chars(expected, &f)?;
+
+            if height == 0 {
+                width = row.len();
+            } else if row.len() != width {
+                return Err(line.error(line.text, format!("expected {} columns", width)));
+            }
+
+            cells.extend(row);
+            height += 1;
+        }
+
+        if width == 0 {
+            return Err(Error::unexpected_end(self.day, self.first_line, "a grid"));
+        }
+
+        Ok(Grid::new(width, height, cells))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("\u{feff}a \r\n\r\nb\t\r\n\r\n  \n"), "a\n\nb");
+        assert!(matches!(normalize("a\n\nb\n"), Cow::Borrowed("a\n\nb")));
+    }
+
+    #[test]
+    fn test_section_lines() {
+        let input = Input::new(4, "a\r\nb\r\n\r\nc\r\n \r\n\r\nd\r\ne\r\n");
+        let sections: Vec<(usize, String)> = input
+            .sections()
+            .map(|section| (section.first_line(), section.text().to_string()))
+            .collect();
+
+        assert_eq!(sections, vec![(1, "a\nb".into()), (4, "c".into()), (7, "d\ne".into())]);
+    }
+
+    #[test]
+    fn test_token_location() {
+        let input = Input::new(4, "a\nbyr:1 hgt");
+        let line = input.lines().nth(1).unwrap();
+        let token = line.text.split_whitespace().nth(1).unwrap();
+
+        assert_eq!(line.error(token, "bad"), Error::parse(4, 2, 7, "hgt", "bad"));
+    }
+
+    #[test]
+    fn test_grid_ragged() {
+        let error = Input::new(3, "..\n.").grid("'.'", |c| Some(c).filter(|&c| c == '.'));
+
+        assert_eq!(error.map(|_| ()), Err(Error::parse(3, 2, 1, ".", "expected 2 columns")));
+    }
+}
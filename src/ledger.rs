@@ -0,0 +1,128 @@
+//! Known-good answers, keyed by year, day, part and a hash of the input.
+//!
+//! The ledger is stored as a JSON list of entries so that it diffs nicely when
+//! new answers are recorded.
+
+use std::{
+    collections::{btree_map, BTreeMap},
+    fs,
+    hash::Hasher,
+    io::{self, ErrorKind},
+    iter::FromIterator,
+    path::Path,
+};
+
+use fnv::FnvHasher;
+use serde::{Deserialize, Serialize};
+
+use crate::input;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Defaults to 2020, for ledgers written before there were other years.
+    #[serde(default = "first_year")]
+    pub year: u16,
+    pub day: u32,
+    pub part: u32,
+    /// The [`input_hash`] of the input the answer was found for.
+    pub input: String,
+    pub answer: String,
+}
+
+/// The year of files written before there were other years.
+pub(crate) fn first_year() -> u16 {
+    2020
+}
+
+/// How an answer compares to the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The answer matches the recorded one.
+    Verified,
+    /// The answer differs from the recorded one.
+    Mismatch(String),
+    /// No answer is recorded for this input.
+    Missing,
+}
+
+#[derive(Debug, Default)]
+pub struct Ledger {
+    answers: BTreeMap<(u16, u32, u32, String), String>,
+}
+
+/// A stable hash of an input, after [normalizing](input::normalize) it so that
+/// e.g. a copy saved with CRLF line endings has the same hash.
+pub fn input_hash(input: &str) -> String {
+    let mut hasher = FnvHasher::default();
+    hasher.write(input::normalize(input).as_bytes());
+    format!("{:016x}", hasher.finish())
+}
+
+impl Ledger {
+    /// Loads the ledger at `path`, which is empty if the file does not exist yet.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+
+        let entries: Vec<Entry> = serde_json::from_str(&contents)
+            .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+
+        Ok(entries.into_iter().collect())
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut json = serde_json::to_string_pretty(&self.entries().collect::<Vec<_>>())?;
+        json.push('\n');
+
+        fs::write(path, json)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = Entry> + '_ {
+        self.answers.iter().map(|((year, day, part, input), answer)| Entry {
+            year: *year,
+            day: *day,
+            part: *part,
+            input: input.clone(),
+            answer: answer.clone(),
+        })
+    }
+
+    pub fn get(&self, year: u16, day: u32, part: u32, input_hash: &str) -> Option<&str> {
+        self.answers
+            .get(&(year, day, part, input_hash.to_string()))
+            .map(String::as_str)
+    }
+
+    pub fn check(&self, year: u16, day: u32, part: u32, input_hash: &str, answer: &str) -> Status {
+        match self.get(year, day, part, input_hash) {
+            Some(expected) if expected == answer => Status::Verified,
+            Some(expected) => Status::Mismatch(expected.to_string()),
+            None => Status::Missing,
+        }
+    }
+
+    /// Records `answer` unless one is already known. Returns whether it was recorded.
+    pub fn record(&mut self, year: u16, day: u32, part: u32, input_hash: &str, answer: &str) -> bool {
+        match self.answers.entry((year, day, part, input_hash.to_string())) {
+            btree_map::Entry::Vacant(slot) => {
+                slot.insert(answer.to_string());
+                true
+            }
+            btree_map::Entry::Occupied(_) => false,
+        }
+    }
+}
+
+impl FromIterator<Entry> for Ledger {
+    fn from_iter<T: IntoIterator<Item = Entry>>(iter: T) -> Self {
+        let answers = iter
+            .into_iter()
+            .map(|entry| ((entry.year, entry.day, entry.part, entry.input), entry.answer))
+            .collect();
+
+        Self { answers }
+    }
+}
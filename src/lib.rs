@@ -1,30 +1,33 @@
+//! Solutions to [Advent of Code](https://adventofcode.com), one module per year.
+//!
+//! Every day is a public module of its year, e.g. [`y2020::day1`], exposing its
+//! parsed input type, a `parse` generator, `part1`/`part2` solvers and whatever
+//! engine the solvers are built on. The [`registry`] lists them all. Generators
+//! never panic on malformed input; they report where it went wrong with an [`Error`].
+//!
+//! `aoc-runner` only handles one year per crate, so only 2020's days are also
+//! registered with it, through the `#[aoc_generator]` and `#[aoc]` attributes.
+
 use aoc_runner_derive::aoc_lib;
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
+pub mod allocations;
+pub mod automaton;
+pub mod bench;
+pub mod differential;
+pub mod error;
+pub mod explain;
+pub mod frames;
+pub mod grid;
+pub mod input;
+pub mod ledger;
+pub mod limits;
+pub mod progress;
+pub mod registry;
+pub mod runner;
+pub mod synth;
+
+pub use error::{Error, Result};
 
+pub mod y2020;
 
 aoc_lib! { year = 2020 }
@@ -0,0 +1,262 @@
+//! Wall-clock and memory budgets for solvers, and cancellation.
+//!
+//! Long-running solvers create a [`Checkpoint`] and tick it as they loop. Under
+//! [`enforce`], a tick which finds the budget spent or the solver cancelled fails
+//! with [`Error::GaveUp`] instead of letting the solver run on. Outside of it, as
+//! when `aoc-runner` calls the solvers, checkpoints never fail. Checkpoints also
+//! report the solver's [progress](crate::progress).
+//!
+//! Budgets are cooperative: nothing stops a solver which never checks them. Each day
+//! in the [registry](crate::registry) lists the [`Limit`]s its solvers check, so that
+//! the runner can warn when a limit is set for a day which would ignore it.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    progress::{self, Progress},
+    Error, Result,
+};
+
+/// How many ticks pass between two checks of the budget by default, so that
+/// ticking stays cheap in tight loops.
+const CHECK_EVERY: u32 = 1 << 16;
+
+/// Cancels the solver it was handed to, at its next check.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A budget which solvers check for themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    /// Checked as the solver ticks its [`Checkpoint`].
+    Time,
+    /// Checked by [`Checkpoint::reserve`].
+    Memory,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Limit::Time => write!(f, "time limit"),
+            Limit::Memory => write!(f, "memory limit"),
+        }
+    }
+}
+
+/// A budget for each generator and solver call.
+#[derive(Debug, Clone, Default)]
+pub struct Limits {
+    pub time: Option<Duration>,
+    /// In bytes, checked against the large allocations solvers [reserve](Checkpoint::reserve)
+    /// up front.
+    pub memory: Option<usize>,
+    pub cancel: CancelToken,
+}
+
+impl Limits {
+    /// The limits which are set.
+    pub fn active(&self) -> Vec<Limit> {
+        let mut active = Vec::new();
+
+        if self.time.is_some() {
+            active.push(Limit::Time);
+        }
+
+        if self.memory.is_some() {
+            active.push(Limit::Memory);
+        }
+
+        active
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Budget {
+    limits: Limits,
+    deadline: Option<Instant>,
+}
+
+thread_local! {
+    static BUDGET: RefCell<Option<Budget>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous budget, even if the call under budget panicked.
+struct Restore(Option<Budget>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        BUDGET.with(|budget| *budget.borrow_mut() = previous);
+    }
+}
+
+/// Calls `f` with `limits` applying to the checkpoints it creates on this thread.
+/// The time limit counts from now.
+pub fn enforce<T>(limits: &Limits, f: impl FnOnce() -> T) -> T {
+    let budget = Budget {
+        limits: limits.clone(),
+        deadline: limits.time.map(|time| Instant::now() + time),
+    };
+
+    let _restore = Restore(BUDGET.with(|current| current.replace(Some(budget))));
+
+    f()
+}
+
+/// Where a solver checks its budget and reports its progress.
+#[derive(Debug)]
+pub struct Checkpoint {
+    day: u32,
+    ticks: u32,
+    stride: u32,
+    done: u64,
+    total: Option<u64>,
+    started: Instant,
+    budget: Option<Budget>,
+    reports: bool,
+}
+
+impl Checkpoint {
+    /// A checkpoint for the budget `day`'s solver is running under on this thread.
+    pub fn new(day: u32) -> Self {
+        Self {
+            day,
+            ticks: 0,
+            stride: CHECK_EVERY,
+            done: 0,
+            total: None,
+            started: Instant::now(),
+            budget: BUDGET.with(|budget| budget.borrow().clone()),
+            reports: true,
+        }
+    }
+
+    /// A checkpoint which never fails nor reports progress, for code which can run
+    /// both under a budget and without one.
+    pub fn unlimited() -> Self {
+        Self {
+            budget: None,
+            reports: false,
+            ..Self::new(0)
+        }
+    }
+
+    /// Sets how many iterations the loop will run, for estimating the time left.
+    pub fn with_total(self, total: u64) -> Self {
+        Self {
+            total: Some(total),
+            ..self
+        }
+    }
+
+    /// Checks every `stride` ticks instead, e.g. every tick for loops whose
+    /// iterations are slow.
+    pub fn with_stride(self, stride: u32) -> Self {
+        Self {
+            stride: stride.max(1),
+            ..self
+        }
+    }
+
+    /// Counts one iteration, checking the budget every so often.
+    #[inline]
+    pub fn tick(&mut self) -> Result<()> {
+        self.ticks += 1;
+
+        if self.ticks >= self.stride {
+            self.done += u64::from(self.ticks);
+            self.ticks = 0;
+            self.check()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Reports progress and checks the budget now.
+    pub fn check(&self) -> Result<()> {
+        if !self.reports {
+            return Ok(());
+        }
+
+        progress::offer(|| Progress {
+            day: self.day,
+            done: self.done + u64::from(self.ticks),
+            total: self.total,
+            elapsed: self.started.elapsed(),
+        });
+
+        let budget = match &self.budget {
+            Some(budget) => budget,
+            None => return Ok(()),
+        };
+
+        if budget.limits.cancel.is_cancelled() {
+            return Err(self.give_up("cancelled"));
+        }
+
+        match (budget.deadline, budget.limits.time) {
+            (Some(deadline), Some(time)) if Instant::now() >= deadline => {
+                Err(self.give_up(format!("exceeded the time limit of {:?}", time)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that `bytes` more for `what` fit in the memory budget.
+    pub fn reserve(&self, bytes: usize, what: &str) -> Result<()> {
+        match self.budget.as_ref().and_then(|budget| budget.limits.memory) {
+            Some(memory) if bytes > memory => Err(self.give_up(format!(
+                "{} needs {} bytes, over the memory limit of {}",
+                what, bytes, memory
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// An error for giving up on this day.
+    pub fn give_up(&self, reason: impl fmt::Display) -> Error {
+        Error::gave_up(self.day, reason)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cancel() {
+        let limits = Limits::default();
+
+        let result = enforce(&limits, || {
+            let mut checkpoint = Checkpoint::new(1);
+            limits.cancel.cancel();
+
+            (0..CHECK_EVERY).try_for_each(|_| checkpoint.tick())
+        });
+
+        assert_eq!(result, Err(Error::gave_up(1, "cancelled")));
+        assert!(Checkpoint::new(1).check().is_ok());
+    }
+}
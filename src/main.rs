@@ -1,3 +1,1737 @@
-use aoc_runner_derive::aoc_main;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, BufRead, BufReader, IsTerminal, Read},
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-aoc_main! { lib = advent_of_code_2020 }
\ No newline at end of file
+use advent_of_code_2020::{
+    allocations::{self, Allocations},
+    bench::{self, Baseline, Target},
+    differential::{self, Divergence},
+    explain::Note,
+    frames::{self, Recorder},
+    ledger::{self, Ledger, Status},
+    limits::{self, Limits},
+    progress::{self, Progress},
+    registry::{self, Day},
+    runner::{self, Outcome},
+    y2020,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static COUNTER: allocations::Counter = allocations::Counter;
+
+use itertools::Itertools;
+use serde::Serialize;
+
+#[derive(Parser)]
+#[command(about = "Solves Advent of Code puzzles")]
+struct Cli {
+    /// How to print results
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+
+    /// File of known-good answers
+    #[arg(long, default_value = "answers.json", global = true)]
+    ledger: PathBuf,
+
+    /// Seconds each solver may run before giving up. Solvers check it themselves as they
+    /// loop; days which never do are warned about and run to the end
+    #[arg(long, global = true)]
+    time_limit: Option<u64>,
+
+    /// Megabytes each solver may allocate up front before giving up. Solvers check it
+    /// themselves; days which never do are warned about and run to the end
+    #[arg(long, global = true)]
+    memory_limit: Option<usize>,
+
+    /// Milliseconds between updates of the progress bar, drawn when stderr is a terminal
+    #[arg(long, default_value_t = 100, global = true)]
+    progress_interval: u64,
+
+    /// Never draw a progress bar
+    #[arg(long, global = true)]
+    no_progress: bool,
+
+    /// Show the intermediate results each solver noted on the way to its answer
+    #[arg(long, global = true)]
+    explain: bool,
+
+    /// Only this year, which is also the year of --day [default: every year, or the latest with --day]
+    #[arg(long, global = true)]
+    year: Option<u16>,
+
+    /// Show how much each generator and solver allocated (needs the count-allocations feature)
+    #[arg(long, global = true)]
+    allocations: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day
+    Run {
+        #[arg(long)]
+        day: u32,
+
+        /// Only solve this part
+        #[arg(long)]
+        part: Option<u32>,
+
+        /// Input file, or `-` for stdin [default: <input-dir>/<year>/day<N>.txt and day<N>/*.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// Directory of inputs, with a subdirectory per year
+        #[arg(long, default_value = "input")]
+        input_dir: PathBuf,
+
+        /// The input is already parsed, as JSON in the form `parse` prints
+        #[arg(long, requires = "input")]
+        json: bool,
+
+        /// Add answers for inputs not yet in the ledger
+        #[arg(long)]
+        record: bool,
+
+        #[command(flatten)]
+        frames: FrameArgs,
+    },
+    /// Solve every day
+    All {
+        /// Directory of inputs, with a subdirectory per year
+        #[arg(long, default_value = "input")]
+        input_dir: PathBuf,
+
+        /// Add answers for inputs not yet in the ledger
+        #[arg(long)]
+        record: bool,
+
+        /// Solve this many days at once on separate threads (0 for one per CPU), then
+        /// print a summary table
+        #[arg(long)]
+        jobs: Option<usize>,
+    },
+    /// Solve every day and compare the answers against the ledger
+    Verify {
+        /// Directory of inputs, with a subdirectory per year
+        #[arg(long, default_value = "input")]
+        input_dir: PathBuf,
+    },
+    /// Time generators and solvers separately over many iterations
+    Bench {
+        /// Only benchmark this day
+        #[arg(long)]
+        day: Option<u32>,
+
+        /// Only benchmark this part
+        #[arg(long, requires = "day")]
+        part: Option<u32>,
+
+        #[arg(long, default_value_t = 10)]
+        iterations: usize,
+
+        /// Seconds after which to stop sampling a target early
+        #[arg(long, default_value_t = 5.0)]
+        budget: f64,
+
+        /// Directory of inputs, with a subdirectory per year
+        #[arg(long, default_value = "input")]
+        input_dir: PathBuf,
+
+        /// Save the measurements as a baseline
+        #[arg(long)]
+        save: Option<PathBuf>,
+
+        /// Compare against a saved baseline
+        #[arg(long)]
+        compare: Option<PathBuf>,
+
+        /// Flag targets whose median is this many percent slower than the baseline
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Check solvers which rely on a trick against straightforward reference solvers,
+    /// on synthesized inputs. Under --time-limit, inputs a solver gives up on are skipped
+    Differential {
+        /// Only check this day
+        #[arg(long)]
+        day: Option<u32>,
+
+        /// How many inputs to synthesize, from seeds 0, 1, 2...
+        #[arg(long, default_value_t = 100)]
+        seeds: u64,
+
+        /// Roughly how many items (lines, groups, tiles...) each input has
+        #[arg(long, default_value_t = 20)]
+        size: usize,
+    },
+    /// Print what a day's generator makes of an input
+    Parse {
+        #[arg(long)]
+        day: u32,
+
+        /// Input file, or `-` for stdin [default: <input-dir>/<year>/day<N>.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// Directory of inputs, with a subdirectory per year
+        #[arg(long, default_value = "input")]
+        input_dir: PathBuf,
+
+        /// How to print the parsed input
+        #[arg(long, value_enum, default_value_t = Emit::Json)]
+        emit: Emit,
+    },
+    /// Find entries of an expense report, a day 1 input, which add up to a target
+    Expenses {
+        /// Input file, or `-` for stdin [default: <input-dir>/2020/day1.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// Directory of inputs, with a subdirectory per year
+        #[arg(long, default_value = "input")]
+        input_dir: PathBuf,
+
+        /// The sum to find
+        #[arg(long, default_value_t = y2020::day1::TARGET, allow_negative_numbers = true)]
+        target: i32,
+
+        /// How many entries to add up
+        #[arg(short, default_value_t = 2)]
+        k: usize,
+
+        /// Print every combination instead of the first found
+        #[arg(long)]
+        all: bool,
+
+        /// Print every set of values which adds up to the target, with how many
+        /// combinations of entries have it, instead of the entries themselves. Reads
+        /// the input as a stream, for reports too long to hold in memory
+        #[arg(long, conflicts_with = "all")]
+        distinct: bool,
+
+        /// Only print how many combinations of entries add up to the target. Reads
+        /// the input as a stream, like --distinct
+        #[arg(long, conflicts_with_all = ["all", "distinct"])]
+        count: bool,
+    },
+    /// Check a password database, a day 2 input, against policies
+    Passwords {
+        /// Input file, or `-` for stdin [default: <input-dir>/2020/day2.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// Directory of inputs, with a subdirectory per year
+        #[arg(long, default_value = "input")]
+        input_dir: PathBuf,
+
+        /// A policy to check, e.g. `count and (min-length 12 or classes lower,upper,digit)`.
+        /// The rules are count, positions, min-length N, classes C,... of lower, upper,
+        /// digit and symbol, forbid "TEXT" and regex "PATTERN". Positions and lengths
+        /// count chars unless followed by `graphemes`
+        #[arg(long, required = true)]
+        policy: Vec<String>,
+
+        /// The format of the database
+        #[arg(long, value_enum, default_value_t = Dialect::Aoc)]
+        dialect: Dialect,
+
+        /// List the entries each policy does not allow, with the rules they break
+        #[arg(long)]
+        violations: bool,
+
+        /// Report how many entries fail each policy by their character, and on which lines
+        #[arg(long)]
+        audit: bool,
+    },
+    /// Generate a random valid input for a day
+    Synth {
+        #[arg(long)]
+        day: u32,
+
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Roughly how many items (lines, groups, tiles...) the input has
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+
+        /// Write the input to this file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Print the answers known by construction, to stderr if the input goes to stdout
+        #[arg(long)]
+        answers: bool,
+
+        /// Add the answers known by construction to the ledger
+        #[arg(long)]
+        record: bool,
+    },
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum Emit {
+    Json,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum Dialect {
+    Aoc,
+    Csv,
+    JsonLines,
+}
+
+impl From<Dialect> for y2020::day2::Dialect {
+    fn from(dialect: Dialect) -> Self {
+        match dialect {
+            Dialect::Aoc => y2020::day2::Dialect::Aoc,
+            Dialect::Csv => y2020::day2::Dialect::Csv,
+            Dialect::JsonLines => y2020::day2::Dialect::JsonLines,
+        }
+    }
+}
+
+#[derive(Args)]
+struct FrameArgs {
+    /// Record each state of the day's simulation, in this format
+    #[arg(long, value_enum)]
+    frames: Option<FrameFormat>,
+
+    /// Where to write the recordings, as day<N>-part<P> files or directories
+    #[arg(long, default_value = "frames")]
+    frames_dir: PathBuf,
+
+    /// Only keep every Nth state
+    #[arg(long, default_value_t = 1)]
+    frames_every: usize,
+
+    /// Stop recording after this many frames
+    #[arg(long, default_value_t = 500)]
+    frames_limit: usize,
+
+    /// Pixels per cell in image formats
+    #[arg(long, default_value_t = 4)]
+    frames_scale: usize,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum FrameFormat {
+    Ascii,
+    Pgm,
+    Ppm,
+    Gif,
+}
+
+impl From<FrameFormat> for frames::Format {
+    fn from(format: FrameFormat) -> Self {
+        match format {
+            FrameFormat::Ascii => frames::Format::Ascii,
+            FrameFormat::Pgm => frames::Format::Pgm,
+            FrameFormat::Ppm => frames::Format::Ppm,
+            FrameFormat::Gif => frames::Format::Gif,
+        }
+    }
+}
+
+/// A result as printed, including failures to read the input.
+#[derive(Serialize)]
+struct Record {
+    year: u16,
+    day: u32,
+    part: u32,
+    /// The input file, relative to the input directory.
+    input: String,
+    #[serde(skip)]
+    hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<String>,
+    #[serde(rename = "generator_secs", serialize_with = "as_secs")]
+    generator: Duration,
+    #[serde(rename = "solver_secs", serialize_with = "as_secs")]
+    solver: Duration,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    notes: Vec<Note>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generator_allocations: Option<Allocations>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    solver_allocations: Option<Allocations>,
+}
+
+fn as_secs<S: serde::Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+impl From<Outcome> for Record {
+    fn from(outcome: Outcome) -> Self {
+        let (answer, error) = match outcome.answer {
+            Ok(answer) => (Some(answer), None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+
+        Record {
+            year: outcome.year,
+            day: outcome.day,
+            part: outcome.part,
+            input: String::new(),
+            hash: None,
+            answer,
+            error,
+            status: None,
+            expected: None,
+            generator: outcome.generator,
+            solver: outcome.solver,
+            notes: outcome.notes,
+            generator_allocations: outcome.generator_allocations,
+            solver_allocations: outcome.solver_allocations,
+        }
+    }
+}
+
+fn read_input(path: &Path) -> Result<String, String> {
+    let mut input = String::new();
+
+    let result = if path == Path::new("-") {
+        io::stdin().read_to_string(&mut input).map(|_| ())
+    } else {
+        fs::read_to_string(path).map(|contents| input = contents)
+    };
+
+    result.map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+
+    Ok(input)
+}
+
+/// Where the inputs for `day`'s year are, e.g. `input/2020`.
+fn year_dir(input_dir: &Path, day: &Day) -> PathBuf {
+    input_dir.join(day.year.to_string())
+}
+
+fn default_input(input_dir: &Path, day: &Day) -> PathBuf {
+    year_dir(input_dir, day).join(format!("day{}.txt", day.day))
+}
+
+/// Every input for `day`: `<input-dir>/<year>/day<N>.txt`, then each
+/// `<input-dir>/<year>/day<N>/*.txt` in name order. Falls back to the first so that a
+/// missing input is reported.
+fn day_inputs(input_dir: &Path, day: &Day) -> Vec<PathBuf> {
+    let single = default_input(input_dir, day);
+
+    let mut inputs: Vec<PathBuf> = fs::read_dir(year_dir(input_dir, day).join(format!("day{}", day.day)))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    inputs.sort();
+
+    if single.exists() || inputs.is_empty() {
+        inputs.insert(0, single);
+    }
+
+    inputs
+}
+
+/// How an input is shown: relative to the directory of its year, without `.txt`.
+fn input_name(year_dir: &Path, path: &Path) -> String {
+    let name = path.strip_prefix(year_dir).unwrap_or(path).display().to_string();
+
+    match name.strip_suffix(".txt") {
+        Some(name) => name.to_string(),
+        None => name,
+    }
+}
+
+/// Solves `part` with its tracing solver, and writes the frames it records to a file
+/// named after the day and the stem of the input at `path`.
+fn trace(
+    day: &Day,
+    input: &str,
+    path: &Path,
+    part: u32,
+    limits: &Limits,
+    args: &FrameArgs,
+    format: FrameFormat,
+) -> Outcome {
+    let mut recorder = Recorder::new(args.frames_every, args.frames_limit);
+    let outcome = runner::run_traced(day, input, part, limits, &mut recorder);
+
+    let format = frames::Format::from(format);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut name = format!("day{}", day.day);
+
+    if stem != name {
+        name = format!("{}-{}", name, stem);
+    }
+
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+    let mut path = args.frames_dir.join(format!("{}-part{}", name, part));
+
+    if let Some(extension) = format.extension() {
+        path.set_extension(extension);
+    }
+
+    let written = fs::create_dir_all(&args.frames_dir)
+        .and_then(|_| frames::export(recorder.frames(), format, &path, args.frames_scale));
+
+    match written {
+        Ok(()) => eprintln!("wrote {} frames to {}", recorder.frames().len(), path.display()),
+        Err(err) => eprintln!("cannot write frames to {}: {}", path.display(), err),
+    }
+
+    outcome
+}
+
+/// Solves `parts` for the input at `path`, shown as `name`. With `json`, the input
+/// is the generator's output as `parse` prints it.
+fn solve(
+    day: &Day,
+    path: &Path,
+    name: &str,
+    parts: &[u32],
+    limits: &Limits,
+    frames: Option<&FrameArgs>,
+    json: bool,
+) -> Vec<Record> {
+    match read_input(path) {
+        Ok(input) => {
+            let hash = ledger::input_hash(&input);
+
+            let outcomes = match frames {
+                Some(args @ FrameArgs { frames: Some(format), .. }) => parts
+                    .iter()
+                    .map(|&part| trace(day, &input, path, part, limits, args, *format))
+                    .collect(),
+                _ if json => runner::run_json(day, &input, parts, limits),
+                _ => runner::run(day, &input, parts, limits),
+            };
+
+            outcomes
+                .into_iter()
+                .map(|outcome| Record {
+                    input: name.to_string(),
+                    hash: Some(hash.clone()),
+                    ..Record::from(outcome)
+                })
+                .collect()
+        }
+        Err(err) => parts
+            .iter()
+            .map(|&part| Record {
+                year: day.year,
+                day: day.day,
+                part,
+                input: name.to_string(),
+                hash: None,
+                answer: None,
+                error: Some(err.clone()),
+                status: None,
+                expected: None,
+                generator: Duration::default(),
+                solver: Duration::default(),
+                notes: Vec::new(),
+                generator_allocations: None,
+                solver_allocations: None,
+            })
+            .collect(),
+    }
+}
+
+/// Solves `parts` for every input of `day`.
+fn solve_batch(
+    day: &Day,
+    input_dir: &Path,
+    parts: &[u32],
+    limits: &Limits,
+    frames: Option<&FrameArgs>,
+) -> Vec<Record> {
+    day_inputs(input_dir, day)
+        .iter()
+        .flat_map(|path| {
+            let name = input_name(&year_dir(input_dir, day), path);
+            solve(day, path, &name, parts, limits, frames, false)
+        })
+        .collect()
+}
+
+/// Solves every part of `days`, `jobs` days at a time. The records are in the order
+/// of `days` either way, and the progress of every job goes to this thread's watcher.
+fn solve_all(days: &[&Day], input_dir: &Path, limits: &Limits, jobs: usize) -> Vec<Record> {
+    let solve_day = |day: &Day| {
+        let parts: Vec<u32> = day.parts().collect();
+        solve_batch(day, input_dir, &parts, limits, None)
+    };
+
+    if jobs <= 1 {
+        return days.iter().flat_map(|day| solve_day(day)).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    let mut solved: Vec<(usize, Vec<Record>)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.min(days.len()))
+            .map(|_| {
+                let sender = sender.clone();
+
+                scope.spawn(|| {
+                    let report = move |progress: &Progress| {
+                        // The main thread only stops listening once every worker is done.
+                        let _ = sender.send(*progress);
+                    };
+
+                    progress::watch(Duration::ZERO, report, || {
+                        let mut solved = Vec::new();
+
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+
+                            match days.get(index) {
+                                Some(day) => solved.push((index, solve_day(day))),
+                                None => break solved,
+                            }
+                        }
+                    })
+                })
+            })
+            .collect();
+
+        // Until every worker is done and has dropped its sender.
+        drop(sender);
+        receiver.iter().for_each(progress::forward);
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("worker panicked"))
+            .collect()
+    });
+
+    solved.sort_by_key(|&(index, _)| index);
+    solved.into_iter().flat_map(|(_, records)| records).collect()
+}
+
+fn load_ledger(path: &Path) -> Ledger {
+    Ledger::load(path).unwrap_or_else(|err| {
+        eprintln!("cannot load ledger {}: {}", path.display(), err);
+        process::exit(2);
+    })
+}
+
+/// Adds every new answer to the ledger at `path`.
+fn record(records: &[Record], path: &Path) {
+    let mut ledger = load_ledger(path);
+
+    let recorded = records
+        .iter()
+        .filter_map(|record| Some((record, record.hash.as_ref()?, record.answer.as_ref()?)))
+        .filter(|(record, input, answer)| ledger.record(record.year, record.day, record.part, input, answer))
+        .count();
+
+    if let Err(err) = ledger.save(path) {
+        eprintln!("cannot save ledger {}: {}", path.display(), err);
+        process::exit(2);
+    }
+
+    eprintln!("recorded {} new answers in {}", recorded, path.display());
+}
+
+/// Compares every answer against the ledger at `path`.
+fn verify(records: &mut [Record], path: &Path) {
+    let ledger = load_ledger(path);
+
+    for record in records {
+        if let (Some(input), Some(answer)) = (&record.hash, &record.answer) {
+            let (status, expected) = match ledger.check(record.year, record.day, record.part, input, answer) {
+                Status::Verified => ("verified", None),
+                Status::Mismatch(expected) => ("mismatch", Some(expected)),
+                Status::Missing => ("missing", None),
+            };
+
+            record.status = Some(status);
+            record.expected = expected;
+        }
+    }
+}
+
+impl Record {
+    /// What the generator and solver allocated, one line each.
+    fn allocation_lines(&self) -> Vec<String> {
+        let line = |what: &str, allocations: &Allocations| {
+            format!(
+                "{} allocations: {}, {}B, peak {}B",
+                what,
+                compact(allocations.count as f64),
+                compact(allocations.bytes as f64),
+                compact(allocations.peak as f64)
+            )
+        };
+
+        let generator = self.generator_allocations.map(|allocations| line("generator", &allocations));
+        let solver = self.solver_allocations.map(|allocations| line("solver", &allocations));
+
+        generator.into_iter().chain(solver).collect()
+    }
+
+    fn status_text(&self) -> String {
+        match (self.status, &self.expected) {
+            (Some("mismatch"), Some(expected)) => format!(" [MISMATCH, expected {}]", expected),
+            (Some(status), _) => format!(" [{}]", status),
+            (None, _) => String::new(),
+        }
+    }
+}
+
+/// Prints the records of a day with several inputs as a table with a row per input.
+fn print_table(day: u32, records: &[&Record]) {
+    let inputs: Vec<&str> = records.iter().map(|record| record.input.as_str()).unique().collect();
+    let parts: Vec<u32> = records.iter().map(|record| record.part).unique().collect();
+
+    let cell = |input: &str, part: u32| {
+        let record = records
+            .iter()
+            .find(|record| record.input == input && record.part == part);
+
+        match record {
+            Some(record @ Record { answer: Some(answer), .. }) => format!("{}{}", answer, record.status_text()),
+            Some(_) => "FAILED".to_string(),
+            None => String::new(),
+        }
+    };
+
+    let mut rows = vec![std::iter::once("input".to_string())
+        .chain(parts.iter().map(|part| format!("part {}", part)))
+        .collect::<Vec<_>>()];
+
+    rows.extend(inputs.iter().map(|&input| {
+        std::iter::once(input.to_string())
+            .chain(parts.iter().map(|&part| cell(input, part)))
+            .collect()
+    }));
+
+    let widths: Vec<usize> = (0..=parts.len())
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or_default())
+        .collect();
+
+    println!("Day {}:", day);
+
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+            .join("  ");
+
+        println!("  {}", line.trim_end());
+    }
+
+    for record in records {
+        if let Some(error) = &record.error {
+            println!("  {} part {}: FAILED: {}", record.input, record.part, error);
+        }
+
+        for note in &record.notes {
+            println!("  {} part {}: {}", record.input, record.part, note);
+        }
+
+        for line in record.allocation_lines() {
+            println!("  {} part {}: {}", record.input, record.part, line);
+        }
+    }
+}
+
+/// Prints a header before each year's results, if there are results from several.
+struct YearHeaders {
+    several: bool,
+    current: Option<u16>,
+}
+
+impl YearHeaders {
+    fn new(years: impl Iterator<Item = u16>) -> Self {
+        YearHeaders {
+            several: years.unique().count() > 1,
+            current: None,
+        }
+    }
+
+    fn print(&mut self, year: u16) {
+        if self.several && self.current != Some(year) {
+            println!("Year {}:", year);
+            self.current = Some(year);
+        }
+    }
+}
+
+fn print(records: &[Record], format: Format) {
+    match format {
+        Format::Text => {
+            let mut headers = YearHeaders::new(records.iter().map(|record| record.year));
+
+            for ((year, day), records) in &records.iter().group_by(|record| (record.year, record.day)) {
+                let records: Vec<&Record> = records.collect();
+                headers.print(year);
+
+                if records.iter().map(|record| &record.input).unique().count() > 1 {
+                    print_table(day, &records);
+                    continue;
+                }
+
+                for record in records {
+                    match (&record.answer, &record.error) {
+                        (Some(answer), _) => println!(
+                            "Day {} - Part {}: {}{}\n\tgenerator: {:?},\n\tsolver: {:?}",
+                            record.day,
+                            record.part,
+                            answer,
+                            record.status_text(),
+                            record.generator,
+                            record.solver
+                        ),
+                        (None, error) => println!(
+                            "Day {} - Part {}: FAILED: {}",
+                            record.day,
+                            record.part,
+                            error.as_deref().unwrap_or_default()
+                        ),
+                    }
+
+                    for note in &record.notes {
+                        println!("\t{}", note);
+                    }
+
+                    for line in record.allocation_lines() {
+                        println!("\t{}", line);
+                    }
+                }
+            }
+        }
+        Format::Json => {
+            println!("{}", serde_json::to_string_pretty(records).unwrap());
+        }
+    }
+}
+
+/// How many threads `--jobs` asks for, where 0 is one per CPU.
+fn jobs_count(jobs: Option<usize>) -> usize {
+    match jobs {
+        Some(0) => thread::available_parallelism().map_or(1, usize::from),
+        Some(jobs) => jobs,
+        None => 1,
+    }
+}
+
+/// Prints one row per part with its answer, times and status, then the totals.
+fn print_summary(records: &[Record], wall_clock: Duration, jobs: usize) {
+    let several_years = records.iter().map(|record| record.year).unique().count() > 1;
+    let several_inputs = records
+        .iter()
+        .map(|record| (record.year, record.day, record.part))
+        .unique()
+        .count()
+        < records.len();
+
+    let mut header = vec!["day", "part", "answer", "generator", "solver", "status"];
+
+    if several_inputs {
+        header.insert(2, "input");
+    }
+
+    if several_years {
+        header.insert(0, "year");
+    }
+
+    let mut rows = vec![header.into_iter().map(str::to_string).collect::<Vec<_>>()];
+
+    rows.extend(records.iter().map(|record| {
+        let status = match (&record.error, record.status) {
+            (Some(_), _) => "FAILED",
+            (None, Some(status)) => status,
+            (None, None) => "ok",
+        };
+
+        let mut row = vec![
+            record.day.to_string(),
+            record.part.to_string(),
+            record.answer.clone().unwrap_or_default(),
+            format!("{:.1?}", record.generator),
+            format!("{:.1?}", record.solver),
+            status.to_string(),
+        ];
+
+        if several_inputs {
+            row.insert(2, record.input.clone());
+        }
+
+        if several_years {
+            row.insert(0, record.year.to_string());
+        }
+
+        row
+    }));
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or_default())
+        .collect();
+
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+            .join("  ");
+
+        println!("{}", line.trim_end());
+    }
+
+    for record in records.iter().filter(|record| record.error.is_some()) {
+        let error = record.error.as_deref().unwrap_or_default();
+        println!("Day {} - Part {} ({}): FAILED: {}", record.day, record.part, record.input, error);
+    }
+
+    // Parts of the same input share its generator run.
+    let generators: Duration = records
+        .iter()
+        .unique_by(|record| (record.year, record.day, &record.input))
+        .map(|record| record.generator)
+        .sum();
+    let solvers: Duration = records.iter().map(|record| record.solver).sum();
+
+    println!(
+        "{} parts in {:.2?} wall clock ({:.2?} in generators and solvers, {} {})",
+        records.len(),
+        wall_clock,
+        generators + solvers,
+        jobs,
+        if jobs == 1 { "job" } else { "jobs" }
+    );
+}
+
+/// Shortens large counts, e.g. `12.5M`.
+fn compact(n: f64) -> String {
+    match n {
+        n if n >= 1e9 => format!("{:.1}G", n / 1e9),
+        n if n >= 1e6 => format!("{:.1}M", n / 1e6),
+        n if n >= 1e3 => format!("{:.1}k", n / 1e3),
+        n => format!("{:.0}", n),
+    }
+}
+
+/// Redraws the progress bar on the current line of stderr.
+fn draw_progress(progress: &Progress) {
+    const WIDTH: usize = 30;
+
+    let rate = compact(progress.rate());
+
+    let line = match (progress.fraction(), progress.total) {
+        (Some(fraction), Some(total)) => {
+            let filled = (fraction * WIDTH as f64) as usize;
+            let eta = progress.eta().unwrap_or_default().as_secs_f64();
+
+            format!(
+                "day {:>2} [{}{}] {:>3.0}% {}/{}, {}/s, {:.1}s left",
+                progress.day,
+                "#".repeat(filled),
+                "-".repeat(WIDTH - filled),
+                fraction * 100.0,
+                compact(progress.done as f64),
+                compact(total as f64),
+                rate,
+                eta
+            )
+        }
+        _ => format!("day {:>2}: iteration {}, {}/s", progress.day, compact(progress.done as f64), rate),
+    };
+
+    eprint!("\r{}\x1b[K", line);
+}
+
+/// The days of `year`, or of every year.
+fn days(year: Option<u16>) -> Vec<&'static Day> {
+    match year {
+        Some(year) => select_year(Some(year)).days.iter().collect(),
+        None => registry::days().collect(),
+    }
+}
+
+/// Looks up `year`, or the latest.
+fn select_year(year: Option<u16>) -> &'static registry::Year {
+    match year {
+        Some(year) => registry::year(year).unwrap_or_else(|| {
+            eprintln!("no solutions for {}", year);
+            process::exit(2);
+        }),
+        None => registry::latest(),
+    }
+}
+
+/// Looks up `day` of `year` (or the latest), and either `part` or all of its parts.
+fn select(year: Option<u16>, day: u32, part: Option<u32>) -> (&'static Day, Vec<u32>) {
+    let year = select_year(year);
+
+    let day = year.day(day).unwrap_or_else(|| {
+        eprintln!("no solution for {} day {}", year.year, day);
+        process::exit(2);
+    });
+
+    let parts = match part {
+        Some(part) if day.solver(part).is_some() => vec![part],
+        Some(part) => {
+            eprintln!("no solution for day {} part {}", day.day, part);
+            process::exit(2);
+        }
+        None => day.parts().collect(),
+    };
+
+    (day, parts)
+}
+
+/// Warns about each limit set which some of `days` never check.
+fn warn_unchecked(days: &[&Day], limits: &Limits) {
+    for limit in limits.active() {
+        let unchecked: Vec<u32> = days.iter().filter(|day| !day.checks(limit)).map(|day| day.day).collect();
+
+        match unchecked.as_slice() {
+            [] => (),
+            [day] => eprintln!("warning: day {} never checks the {} and runs to the end", day, limit),
+            days => eprintln!(
+                "warning: days {} never check the {} and run to the end",
+                days.iter().join(", "),
+                limit
+            ),
+        }
+    }
+}
+
+fn limits(cli: &Cli) -> Limits {
+    Limits {
+        time: cli.time_limit.map(Duration::from_secs),
+        memory: cli.memory_limit.map(|megabytes| megabytes << 20),
+        ..Limits::default()
+    }
+}
+
+/// Runs the `run`, `all` and `verify` commands. Returns whether every answer was found
+/// and matched the ledger.
+fn solve_command(cli: &Cli) -> bool {
+    let limits = limits(cli);
+
+    if cli.allocations && !allocations::is_counting() {
+        eprintln!("counting allocations needs a build with the count-allocations feature");
+        process::exit(2);
+    }
+
+    let solve_records = || match &cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            input_dir,
+            json,
+            frames,
+            ..
+        } => {
+            let (day, parts) = select(cli.year, *day, *part);
+            warn_unchecked(&[day], &limits);
+
+            if frames.frames.is_some() && parts.iter().any(|&part| day.tracer(part).is_none()) {
+                eprintln!("day {} does not record frames", day.day);
+                process::exit(2);
+            }
+
+            if frames.frames.is_some() && *json {
+                eprintln!("frames can only be recorded from a text input");
+                process::exit(2);
+            }
+
+            match input {
+                Some(path) => {
+                    let name = path.display().to_string();
+                    solve(day, path, &name, &parts, &limits, Some(frames), *json)
+                }
+                None => solve_batch(day, input_dir, &parts, &limits, Some(frames)),
+            }
+        }
+        Command::All { input_dir, jobs, .. } => {
+            let days = days(cli.year);
+            warn_unchecked(&days, &limits);
+            solve_all(&days, input_dir, &limits, jobs_count(*jobs))
+        }
+        Command::Verify { input_dir } => {
+            let days = days(cli.year);
+            warn_unchecked(&days, &limits);
+            solve_all(&days, input_dir, &limits, 1)
+        }
+        _ => unreachable!(),
+    };
+
+    let start = Instant::now();
+
+    let mut records = if !cli.no_progress && io::stderr().is_terminal() {
+        let interval = Duration::from_millis(cli.progress_interval);
+        let records = progress::watch(interval, draw_progress, solve_records);
+        eprint!("\r\x1b[K");
+        records
+    } else {
+        solve_records()
+    };
+
+    let wall_clock = start.elapsed();
+
+    if !cli.explain {
+        records.iter_mut().for_each(|record| record.notes.clear());
+    }
+
+    if !cli.allocations {
+        for record in &mut records {
+            record.generator_allocations = None;
+            record.solver_allocations = None;
+        }
+    }
+
+    match cli.command {
+        Command::Run { record: true, .. } | Command::All { record: true, .. } => {
+            record(&records, &cli.ledger)
+        }
+        Command::Verify { .. } => verify(&mut records, &cli.ledger),
+        _ => (),
+    }
+
+    match (&cli.command, cli.format) {
+        (Command::All { jobs: Some(jobs), .. }, Format::Text) => {
+            print_summary(&records, wall_clock, jobs_count(Some(*jobs)))
+        }
+        _ => print(&records, cli.format),
+    }
+
+    if let (Command::Verify { .. }, Format::Text) = (&cli.command, cli.format) {
+        let count = |status| {
+            records
+                .iter()
+                .filter(|record| record.status == Some(status))
+                .count()
+        };
+
+        println!(
+            "{} verified, {} mismatched, {} missing, {} failed",
+            count("verified"),
+            count("mismatch"),
+            count("missing"),
+            records
+                .iter()
+                .filter(|record| record.error.is_some())
+                .count()
+        );
+    }
+
+    let mismatched = records
+        .iter()
+        .any(|record| record.status == Some("mismatch"));
+
+    !mismatched && records.iter().all(|record| record.error.is_none())
+}
+
+#[derive(Serialize)]
+struct BenchRecord {
+    #[serde(flatten)]
+    record: bench::Record,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    change: Option<f64>,
+    slower: bool,
+}
+
+/// Runs the `bench` command. Returns whether every target was benchmarked without
+/// slowing down past the threshold.
+fn bench_command(cli: &Cli) -> bool {
+    let Command::Bench {
+        day,
+        part,
+        iterations,
+        budget,
+        input_dir,
+        save,
+        compare,
+        threshold,
+    } = &cli.command
+    else {
+        unreachable!()
+    };
+
+    let selected: Vec<(&Day, Vec<u32>)> = match day {
+        Some(day) => vec![select(cli.year, *day, *part)],
+        None => days(cli.year)
+            .into_iter()
+            .map(|day| (day, day.parts().collect()))
+            .collect(),
+    };
+
+    let baseline = compare.as_ref().map(|path| {
+        Baseline::load(path).unwrap_or_else(|err| {
+            eprintln!("cannot load baseline {}: {}", path.display(), err);
+            process::exit(2);
+        })
+    });
+
+    let config = bench::Config {
+        iterations: *iterations,
+        budget: Duration::from_secs_f64(*budget),
+    };
+
+    let mut measurements = Vec::new();
+    let mut failed = false;
+
+    for (day, parts) in selected {
+        let result = read_input(&day_inputs(input_dir, day)[0]).and_then(|input| {
+            bench::bench(day, &input, &parts, &config).map_err(|err| err.to_string())
+        });
+
+        match result {
+            Ok(results) => measurements.extend(results),
+            Err(err) => {
+                eprintln!("Day {}: FAILED: {}", day.day, err);
+                failed = true;
+            }
+        }
+    }
+
+    let records: Vec<BenchRecord> = measurements
+        .iter()
+        .map(|measurement| {
+            let change = baseline
+                .as_ref()
+                .and_then(|baseline| baseline.change(measurement));
+
+            BenchRecord {
+                record: bench::Record::from(measurement),
+                change,
+                slower: change.is_some_and(|change| change * 100.0 > *threshold),
+            }
+        })
+        .collect();
+
+    match cli.format {
+        Format::Text => {
+            let mut headers = YearHeaders::new(measurements.iter().map(|measurement| measurement.year));
+
+            for (measurement, record) in measurements.iter().zip(&records) {
+                headers.print(measurement.year);
+                let target = match measurement.target {
+                    Target::Generator => "generator".to_string(),
+                    Target::Part(part) => format!("part {}", part),
+                };
+
+                let change = match record.change {
+                    Some(change) if record.slower => format!(" [{:+.1}%, SLOWER]", change * 100.0),
+                    Some(change) => format!(" [{:+.1}%]", change * 100.0),
+                    None => String::new(),
+                };
+
+                let stats = measurement.stats;
+
+                println!(
+                    "Day {} - {}: mean {:?}, median {:?}, stddev {:?} ({} samples){}",
+                    measurement.day,
+                    target,
+                    stats.mean,
+                    stats.median,
+                    stats.stddev,
+                    stats.samples,
+                    change
+                );
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&records).unwrap()),
+    }
+
+    if let Some(path) = save {
+        if let Err(err) = Baseline::save(&measurements, path) {
+            eprintln!("cannot save baseline {}: {}", path.display(), err);
+            process::exit(2);
+        }
+    }
+
+    !failed && records.iter().all(|record| !record.slower)
+}
+
+#[derive(Serialize)]
+struct DivergenceRecord {
+    year: u16,
+    day: u32,
+    part: u32,
+    seed: u64,
+    size: usize,
+    solver: String,
+    reference: String,
+}
+
+impl From<Divergence> for DivergenceRecord {
+    fn from(divergence: Divergence) -> Self {
+        let describe = |result: advent_of_code_2020::Result<String>| match result {
+            Ok(answer) => answer,
+            Err(err) => format!("FAILED: {}", err),
+        };
+
+        DivergenceRecord {
+            year: divergence.year,
+            day: divergence.day,
+            part: divergence.part,
+            seed: divergence.seed,
+            size: divergence.size,
+            solver: describe(divergence.solver),
+            reference: describe(divergence.reference),
+        }
+    }
+}
+
+/// Runs the `differential` command. Returns whether every solver agreed with its
+/// reference.
+fn differential_command(cli: &Cli) -> bool {
+    let Command::Differential { day, seeds, size } = &cli.command else {
+        unreachable!()
+    };
+
+    let days: Vec<&Day> = match day {
+        Some(day) => vec![select(cli.year, *day, None).0],
+        None => days(cli.year),
+    };
+
+    let days: Vec<&Day> = days
+        .into_iter()
+        .filter(|day| differential::parts(day).next().is_some())
+        .collect();
+
+    if days.is_empty() {
+        eprintln!("no reference solvers to check against");
+        process::exit(2);
+    }
+
+    let limits = limits(cli);
+    let (mut checked, mut skipped) = (0, 0);
+
+    let records: Vec<DivergenceRecord> = days
+        .iter()
+        .flat_map(|day| {
+            let report = differential::check_all(day, 0..*seeds, *size, &limits);
+            checked += report.checked;
+            skipped += report.skipped;
+            report.divergences
+        })
+        .map(DivergenceRecord::from)
+        .collect();
+
+    match cli.format {
+        Format::Text => {
+            let mut headers = YearHeaders::new(records.iter().map(|record| record.year));
+
+            for record in &records {
+                headers.print(record.year);
+                println!(
+                    "Day {} - Part {}: seed {}, size {}: solver {}, reference {}",
+                    record.day, record.part, record.seed, record.size, record.solver, record.reference
+                );
+            }
+
+            println!("{} inputs checked, {} skipped, {} divergences", checked, skipped, records.len());
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&records).unwrap()),
+    }
+
+    records.is_empty()
+}
+
+/// Runs the `parse` command. Returns whether the input could be parsed.
+fn parse_command(cli: &Cli) -> bool {
+    let Command::Parse {
+        day,
+        input,
+        input_dir,
+        emit,
+    } = &cli.command
+    else {
+        unreachable!()
+    };
+
+    let (day, _) = select(cli.year, *day, None);
+    let path = input.clone().unwrap_or_else(|| default_input(input_dir, day));
+
+    let parsed = read_input(&path).and_then(|input| day.generate(&input).map_err(|err| err.to_string()));
+
+    match (parsed, emit) {
+        (Ok(parsed), Emit::Json) => {
+            println!("{}", serde_json::to_string_pretty(&day.emit(parsed.as_ref())).unwrap());
+            true
+        }
+        (Err(err), _) => {
+            eprintln!("Day {}: FAILED: {}", day.day, err);
+            false
+        }
+    }
+}
+
+/// Runs the `expenses` command. Returns whether any combination was found.
+fn expenses_command(cli: &Cli) -> bool {
+    let Command::Expenses {
+        input,
+        input_dir,
+        target,
+        k,
+        all,
+        distinct,
+        count,
+    } = &cli.command
+    else {
+        unreachable!()
+    };
+
+    let day = registry::year(y2020::YEAR).and_then(|year| year.day(1)).expect("no day 1");
+    let path = input.clone().unwrap_or_else(|| default_input(input_dir, day));
+
+    if *distinct || *count {
+        return expense_groups(cli, &path, *k, *target, *count);
+    }
+
+    let found = read_input(&path)
+        .and_then(|input| y2020::day1::parse(&input).map_err(|err| err.to_string()))
+        .and_then(|nums| {
+            let limits = limits(cli);
+            let search = || match all {
+                true => y2020::day1::k_sum_all(&nums, *k, *target),
+                false => y2020::day1::k_sum(&nums, *k, *target).map(|found| found.into_iter().collect()),
+            };
+
+            limits::enforce(&limits, search).map_err(|err| err.to_string())
+        });
+
+    let combinations = match found {
+        Ok(combinations) => combinations,
+        Err(err) => {
+            eprintln!("Day 1: FAILED: {}", err);
+            return false;
+        }
+    };
+
+    match cli.format {
+        Format::Text => {
+            for combination in &combinations {
+                println!(
+                    "{} (indices {}, product {})",
+                    combination,
+                    combination.indices.iter().join(", "),
+                    combination.product()
+                );
+            }
+
+            if combinations.is_empty() {
+                println!("no {} entries add up to {}", k, target);
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&combinations).unwrap()),
+    }
+
+    !combinations.is_empty()
+}
+
+#[derive(Serialize)]
+struct ExpenseCount {
+    k: usize,
+    target: i32,
+    count: u128,
+}
+
+/// Tallies the expense report at `path` as it reads it, then prints each group of
+/// values adding up to `target` as it is found, or only how many combinations of
+/// entries there are.
+fn expense_groups(cli: &Cli, path: &Path, k: usize, target: i32, count_only: bool) -> bool {
+    let reader: Result<Box<dyn BufRead>, _> = if path == Path::new("-") {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        fs::File::open(path).map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+    };
+
+    let tally = reader
+        .map_err(|err| format!("cannot read {}: {}", path.display(), err))
+        .and_then(|reader| y2020::day1::Tally::read(reader).map_err(|err| err.to_string()));
+
+    let limits = limits(cli);
+    let mut groups = Vec::new();
+
+    let result = tally.and_then(|tally| {
+        limits::enforce(&limits, || -> advent_of_code_2020::Result<u128> {
+            let mut total = 0;
+
+            for group in tally.sums(k, target) {
+                let group = group?;
+                total += group.count;
+
+                if count_only {
+                    continue;
+                } else if cli.format == Format::Text {
+                    println!("{} ({} combinations)", group, group.count);
+                } else {
+                    groups.push(group);
+                }
+            }
+
+            Ok(total)
+        })
+        .map_err(|err| err.to_string())
+    });
+
+    let total = match result {
+        Ok(total) => total,
+        Err(err) => {
+            eprintln!("Day 1: FAILED: {}", err);
+            return false;
+        }
+    };
+
+    match (cli.format, count_only) {
+        (Format::Text, true) => println!("{} combinations of {} entries add up to {}", total, k, target),
+        (Format::Text, false) if total == 0 => println!("no {} entries add up to {}", k, target),
+        (Format::Text, false) => (),
+        (Format::Json, true) => {
+            let count = ExpenseCount { k, target, count: total };
+            println!("{}", serde_json::to_string_pretty(&count).unwrap());
+        }
+        (Format::Json, false) => println!("{}", serde_json::to_string_pretty(&groups).unwrap()),
+    }
+
+    total > 0
+}
+
+#[derive(Serialize)]
+struct PolicyRecord {
+    policy: String,
+    allowed: usize,
+    total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failures_by_char: Option<BTreeMap<char, usize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failed_lines: Option<Vec<usize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    violations: Option<Vec<y2020::day2::Violation>>,
+}
+
+/// Runs the `passwords` command. Returns whether the input and every policy could be
+/// parsed.
+fn passwords_command(cli: &Cli) -> bool {
+    let Command::Passwords {
+        input,
+        input_dir,
+        policy,
+        dialect,
+        violations,
+        audit,
+    } = &cli.command
+    else {
+        unreachable!()
+    };
+
+    let policies: Vec<_> = match policy.iter().map(|spec| y2020::day2::parse_policy(spec)).collect() {
+        Ok(policies) => policies,
+        Err(err) => {
+            eprintln!("invalid policy: {}", err);
+            return false;
+        }
+    };
+
+    let day = registry::year(y2020::YEAR).and_then(|year| year.day(2)).expect("no day 2");
+    let path = input.clone().unwrap_or_else(|| default_input(input_dir, day));
+
+    let parsed = read_input(&path).and_then(|input| {
+        y2020::day2::parse_dialect(&input, (*dialect).into()).map_err(|err| err.to_string())
+    });
+
+    let entries = match parsed {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("Day 2: FAILED: {}", err);
+            return false;
+        }
+    };
+
+    let invalid = policies.iter().find_map(|policy| y2020::day2::validate(&entries, policy.as_ref()).err());
+
+    if let Some(err) = invalid {
+        eprintln!("Day 2: FAILED: {}", err);
+        return false;
+    }
+
+    let records: Vec<PolicyRecord> = policies
+        .iter()
+        .map(|policy| {
+            let report = y2020::day2::audit(&entries, policy.as_ref());
+
+            PolicyRecord {
+                policy: report.policy,
+                allowed: report.allowed,
+                total: report.total,
+                failures_by_char: Some(report.failures_by_char).filter(|_| *audit),
+                failed_lines: Some(report.failed_lines).filter(|_| *audit),
+                violations: Some(y2020::day2::violations(&entries, policy.as_ref())).filter(|_| *violations),
+            }
+        })
+        .collect();
+
+    match cli.format {
+        Format::Text => records.iter().for_each(print_policy),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&records).unwrap()),
+    }
+
+    true
+}
+
+fn print_policy(record: &PolicyRecord) {
+    println!("{}: {} of {} allowed", record.policy, record.allowed, record.total);
+
+    if let Some(failures_by_char) = &record.failures_by_char {
+        let most = failures_by_char.values().copied().max().unwrap_or(0);
+        let width = most.to_string().len();
+
+        // Bars are scaled to at most 50 characters.
+        for (char, &failures) in failures_by_char {
+            let bar = "#".repeat((failures * 50).div_ceil(most));
+            println!("  {} {:>width$} {}", char, failures, bar, width = width);
+        }
+    }
+
+    if let Some(failed_lines) = record.failed_lines.as_ref().filter(|lines| !lines.is_empty()) {
+        println!("  failed on lines {}", failed_lines.iter().join(", "));
+    }
+
+    for violation in record.violations.iter().flatten() {
+        let violated = violation.violated.join(", ");
+        println!("  line {}: {}: {}", violation.line, violation.entry, violated);
+    }
+}
+
+#[derive(Serialize)]
+struct SynthRecord {
+    day: u32,
+    part: u32,
+    answer: Option<String>,
+}
+
+/// Runs the `synth` command. Returns whether the input could be written.
+fn synth_command(cli: &Cli) -> bool {
+    let Command::Synth {
+        day,
+        seed,
+        size,
+        out,
+        answers,
+        record,
+    } = &cli.command
+    else {
+        unreachable!()
+    };
+
+    let (day, parts) = select(cli.year, *day, None);
+    let synthetic = day.synthesize(*seed, *size);
+    let input = format!("{}\n", synthetic.input);
+
+    match out {
+        Some(path) => {
+            if let Err(err) = fs::write(path, &input) {
+                eprintln!("cannot write {}: {}", path.display(), err);
+                return false;
+            }
+        }
+        None => print!("{}", input),
+    }
+
+    if *answers {
+        let records: Vec<SynthRecord> = parts
+            .iter()
+            .map(|&part| SynthRecord {
+                day: day.day,
+                part,
+                answer: synthetic.answer(part).map(str::to_string),
+            })
+            .collect();
+
+        let text = match cli.format {
+            Format::Text => records
+                .iter()
+                .map(|record| {
+                    let answer = record.answer.as_deref().unwrap_or("unknown");
+                    format!("Day {} - Part {}: {}", record.day, record.part, answer)
+                })
+                .join("\n"),
+            Format::Json => serde_json::to_string_pretty(&records).unwrap(),
+        };
+
+        if out.is_some() {
+            println!("{}", text);
+        } else {
+            eprintln!("{}", text);
+        }
+    }
+
+    if *record {
+        let mut ledger = load_ledger(&cli.ledger);
+        let hash = ledger::input_hash(&input);
+
+        let recorded = parts
+            .iter()
+            .filter_map(|&part| Some((part, synthetic.answer(part)?)))
+            .filter(|&(part, answer)| ledger.record(day.year, day.day, part, &hash, answer))
+            .count();
+
+        if let Err(err) = ledger.save(&cli.ledger) {
+            eprintln!("cannot save ledger {}: {}", cli.ledger.display(), err);
+            process::exit(2);
+        }
+
+        eprintln!("recorded {} new answers in {}", recorded, cli.ledger.display());
+    }
+
+    true
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let success = match cli.command {
+        Command::Bench { .. } => bench_command(&cli),
+        Command::Differential { .. } => differential_command(&cli),
+        Command::Expenses { .. } => expenses_command(&cli),
+        Command::Parse { .. } => parse_command(&cli),
+        Command::Passwords { .. } => passwords_command(&cli),
+        Command::Synth { .. } => synth_command(&cli),
+        _ => solve_command(&cli),
+    };
+
+    if !success {
+        process::exit(1);
+    }
+}
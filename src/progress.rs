@@ -0,0 +1,122 @@
+//! Progress reports from long-running solvers.
+//!
+//! Solvers report through the [`Checkpoint`](crate::limits::Checkpoint)s they tick.
+//! Whoever runs them can [`watch`] the reports, e.g. to draw a progress bar. Reports
+//! stay on the thread they were made on, unless [`forward`]ed to another.
+
+use std::{
+    cell::RefCell,
+    time::{Duration, Instant},
+};
+
+/// How far along a solver's loop is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    pub day: u32,
+    /// Iterations done so far.
+    pub done: u64,
+    /// Iterations the loop will run, if it is known in advance.
+    pub total: Option<u64>,
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// Iterations per second so far.
+    pub fn rate(&self) -> f64 {
+        self.done as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    pub fn fraction(&self) -> Option<f64> {
+        self.total
+            .map(|total| (self.done as f64 / total.max(1) as f64).min(1.0))
+    }
+
+    /// The time left at the rate so far.
+    pub fn eta(&self) -> Option<Duration> {
+        let left = self.total?.saturating_sub(self.done) as f64;
+        let rate = self.rate();
+
+        if rate > 0.0 {
+            Some(Duration::from_secs_f64(left / rate))
+        } else {
+            None
+        }
+    }
+}
+
+struct Watcher {
+    interval: Duration,
+    last: Option<Instant>,
+    callback: Box<dyn FnMut(&Progress)>,
+}
+
+thread_local! {
+    static WATCHER: RefCell<Option<Watcher>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous watcher, even if the watched call panicked.
+struct Restore(Option<Watcher>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        WATCHER.with(|watcher| *watcher.borrow_mut() = previous);
+    }
+}
+
+/// Calls `f`, passing the progress reported by solvers it runs on this thread to
+/// `callback`, at most once per `interval`.
+pub fn watch<T>(interval: Duration, callback: impl FnMut(&Progress) + 'static, f: impl FnOnce() -> T) -> T {
+    let watcher = Watcher {
+        interval,
+        last: None,
+        callback: Box::new(callback),
+    };
+
+    let _restore = Restore(WATCHER.with(|current| current.replace(Some(watcher))));
+
+    f()
+}
+
+/// Passes `progress`, reported on another thread, on to this thread's watcher, as
+/// [`offer`] does.
+pub fn forward(progress: Progress) {
+    offer(|| progress)
+}
+
+/// Passes the progress made by `progress` on to the watcher, if there is one and
+/// its interval has passed.
+pub(crate) fn offer(progress: impl FnOnce() -> Progress) {
+    WATCHER.with(|watcher| {
+        if let Some(watcher) = watcher.borrow_mut().as_mut() {
+            let now = Instant::now();
+
+            if watcher.last.is_none_or(|last| now - last >= watcher.interval) {
+                watcher.last = Some(now);
+                (watcher.callback)(&progress());
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::limits::Checkpoint;
+
+    #[test]
+    fn test_watch() {
+        let reports = Rc::new(RefCell::new(Vec::new()));
+        let seen = Rc::clone(&reports);
+
+        watch(Duration::ZERO, move |progress| seen.borrow_mut().push(progress.done), || {
+            let mut checkpoint = Checkpoint::new(1).with_total(5).with_stride(2);
+            (0..5).try_for_each(|_| checkpoint.tick())
+        })
+        .unwrap();
+
+        assert_eq!(*reports.borrow(), vec![2, 4]);
+    }
+}
@@ -0,0 +1,258 @@
+//! A table of every year's days, with their generators and solvers, for calling
+//! them by number.
+//!
+//! These are the same functions the `#[aoc_generator]` and `#[aoc]` attributes
+//! register with `aoc-runner`, with the parsed input type erased so that any day
+//! can be driven from the command line. A new year is a module like [`y2020`],
+//! with a table of its days added to [`YEARS`].
+
+use std::any::Any;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    frames::Recorder,
+    limits::Limit,
+    synth::{Rng, Synthetic},
+    y2020, Error, Result,
+};
+
+/// Parses raw puzzle input into the day's input type.
+pub type Generator = fn(&str) -> Result<Box<dyn Any>>;
+
+/// Converts the output of the day's [`Generator`] to JSON.
+pub type Emitter = fn(&dyn Any) -> serde_json::Value;
+
+/// Reads the output of the day's [`Generator`] back from JSON.
+pub type Loader = fn(&str) -> Result<Box<dyn Any>>;
+
+/// Solves one part given the output of the day's [`Generator`].
+pub type Solver = fn(&dyn Any) -> Result<String>;
+
+/// Like a [`Solver`], but records the states of the simulation it runs.
+pub type Tracer = fn(&dyn Any, &mut Recorder) -> Result<String>;
+
+/// What a solver returns: its answer, or an error if it can give up.
+pub trait Answer {
+    fn into_answer(self) -> Result<String>;
+}
+
+macro_rules! answer {
+    ($($type:ty),*) => {
+        $(impl Answer for $type {
+            fn into_answer(self) -> Result<String> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+answer!(String, i32, i64, i128, u16, u32, u64, usize);
+
+impl<T: Answer> Answer for Result<T> {
+    fn into_answer(self) -> Result<String> {
+        self?.into_answer()
+    }
+}
+
+/// Generates a random input of about the given size.
+pub type Synthesizer = fn(&mut Rng, usize) -> Synthetic;
+
+pub struct Day {
+    pub year: u16,
+    pub day: u32,
+    generator: Generator,
+    emitter: Emitter,
+    loader: Loader,
+    solvers: &'static [Solver],
+    tracers: &'static [Tracer],
+    references: &'static [(u32, Solver)],
+    checks: &'static [Limit],
+    synthesizer: Synthesizer,
+}
+
+impl Day {
+    /// Runs the generator, which normalizes the input itself (see [`Input`](crate::input::Input)).
+    pub fn generate(&self, input: &str) -> Result<Box<dyn Any>> {
+        (self.generator)(input)
+    }
+
+    /// Converts `input`, the output of [`Day::generate`], to JSON.
+    pub fn emit(&self, input: &dyn Any) -> serde_json::Value {
+        (self.emitter)(input)
+    }
+
+    /// Reads an input in the form [`Day::emit`] writes. Only its shape is checked, so
+    /// solvers may fail or panic on an input which breaks the puzzle's rules.
+    pub fn load(&self, json: &str) -> Result<Box<dyn Any>> {
+        (self.loader)(json)
+    }
+
+    /// The solver for `part`, if the day has one.
+    pub fn solver(&self, part: u32) -> Option<Solver> {
+        let index = part.checked_sub(1)?;
+        self.solvers.get(index as usize).copied()
+    }
+
+    /// The tracing solver for `part`, if the day has one.
+    pub fn tracer(&self, part: u32) -> Option<Tracer> {
+        let index = part.checked_sub(1)?;
+        self.tracers.get(index as usize).copied()
+    }
+
+    /// The straightforward solver for `part` which the day's solver is checked against,
+    /// if the day's solver relies on a trick (see [`differential`](crate::differential)).
+    pub fn reference(&self, part: u32) -> Option<Solver> {
+        self.references
+            .iter()
+            .find(|&&(number, _)| number == part)
+            .map(|&(_, reference)| reference)
+    }
+
+    /// Whether the day's solvers check `limit`, rather than running to the end whatever
+    /// it is.
+    pub fn checks(&self, limit: Limit) -> bool {
+        self.checks.contains(&limit)
+    }
+
+    /// Generates a random input, always the same for a given `seed` and `size`.
+    pub fn synthesize(&self, seed: u64, size: usize) -> Synthetic {
+        (self.synthesizer)(&mut Rng::new(seed), size)
+    }
+
+    /// The part numbers which have a solver.
+    pub fn parts(&self) -> impl Iterator<Item = u32> {
+        1..=self.solvers.len() as u32
+    }
+}
+
+/// Recovers the concrete input type produced by `generator`.
+fn downcast<T: 'static>(_generator: fn(&str) -> Result<T>, input: &dyn Any) -> &T {
+    input
+        .downcast_ref()
+        .expect("solver called with the output of another day's generator")
+}
+
+/// Serializes the output of `generator`. Maps are written with sorted keys.
+fn emit<T: Serialize + 'static>(generator: fn(&str) -> Result<T>, input: &dyn Any) -> serde_json::Value {
+    serde_json::to_value(downcast(generator, input)).expect("generated input cannot be serialized")
+}
+
+/// Deserializes the output type of `generator`.
+fn load<T>(day: u32, _generator: fn(&str) -> Result<T>, json: &str) -> Result<Box<dyn Any>>
+where
+    T: DeserializeOwned + 'static,
+{
+    let input: T = serde_json::from_str(json).map_err(|err| Error::from_json(day, json, err))?;
+
+    Ok(Box::new(input))
+}
+
+macro_rules! day {
+    (
+        $year:ident::$day:ident, $number:expr, [$($part:ident),*]
+        $(, traced [$($traced:ident),*])?
+        $(, reference [$($reference_part:literal => $reference:ident),*])?
+        $(, checks [$($limit:ident),*])?
+    ) => {
+        Day {
+            year: $year::YEAR,
+            day: $number,
+            generator: |input| Ok(Box::new($year::$day::parse(input)?)),
+            emitter: |input| emit($year::$day::parse, input),
+            loader: |json| load($number, $year::$day::parse, json),
+            solvers: &[$(|input| {
+                let input = downcast($year::$day::parse, input);
+                $year::$day::$part(input).into_answer()
+            }),*],
+            tracers: &[$($(|input, recorder| {
+                let input = downcast($year::$day::parse, input);
+                $year::$day::$traced(input, recorder).into_answer()
+            }),*)?],
+            references: &[$($(($reference_part, |input| {
+                let input = downcast($year::$day::parse, input);
+                $year::$day::$reference(input).into_answer()
+            })),*)?],
+            checks: &[$($(Limit::$limit),*)?],
+            synthesizer: $year::$day::synthesize,
+        }
+    };
+}
+
+/// One event's days.
+pub struct Year {
+    pub year: u16,
+    pub days: &'static [Day],
+}
+
+impl Year {
+    /// Looks up a day by number.
+    pub fn day(&self, number: u32) -> Option<&'static Day> {
+        self.days.iter().find(|day| day.day == number)
+    }
+}
+
+/// Every year, oldest first.
+pub static YEARS: [Year; 1] = [Year {
+    year: y2020::YEAR,
+    days: &Y2020,
+}];
+
+static Y2020: [Day; 25] = [
+    day!(y2020::day1, 1, [part1, part2], checks [Time]),
+    day!(y2020::day2, 2, [part1, part2]),
+    day!(y2020::day3, 3, [part1, part2]),
+    day!(y2020::day4, 4, [part1, part2]),
+    day!(y2020::day5, 5, [part1, part2], reference [2 => part2_reference]),
+    day!(y2020::day6, 6, [part1, part2]),
+    day!(y2020::day7, 7, [part1, part2]),
+    day!(y2020::day8, 8, [part1, part2]),
+    day!(y2020::day9, 9, [part1, part2], checks [Time]),
+    day!(y2020::day10, 10, [part1, part2], reference [2 => part2_reference]),
+    day!(y2020::day11, 11, [part1, part2], traced [part1_traced, part2_traced], checks [Time]),
+    day!(y2020::day12, 12, [part1, part2]),
+    day!(y2020::day13, 13, [part1, part2]),
+    day!(y2020::day14, 14, [part1, part2], reference [2 => part2_reference], checks [Time]),
+    day!(y2020::day15, 15, [part1, part2], checks [Time, Memory]),
+    day!(y2020::day16, 16, [part1, part2]),
+    day!(y2020::day17, 17, [part1, part2], traced [part1_traced, part2_traced], checks [Time]),
+    day!(y2020::day18, 18, [part1, part2]),
+    day!(y2020::day19, 19, [part1, part2]),
+    day!(y2020::day20, 20, [part1, part2]),
+    day!(y2020::day21, 21, [part1, part2]),
+    day!(y2020::day22, 22, [part1, part2], reference [2 => part2_reference], checks [Time]),
+    day!(y2020::day23, 23, [part1, part2], traced [part1_traced, part2_traced], checks [Time]),
+    day!(y2020::day24, 24, [part1, part2], traced [part1_traced, part2_traced], checks [Time]),
+    day!(y2020::day25, 25, [part1], checks [Time]),
+];
+
+/// Looks up a year.
+pub fn year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|candidate| candidate.year == year)
+}
+
+/// The most recent year, which commands default to.
+pub fn latest() -> &'static Year {
+    YEARS.last().expect("no years")
+}
+
+/// Every day of every year.
+pub fn days() -> impl Iterator<Item = &'static Day> {
+    YEARS.iter().flat_map(|year| year.days)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip() {
+        for day in days() {
+            let input = day.generate(&day.synthesize(0, 20).input).unwrap();
+            let json = day.emit(input.as_ref());
+            let loaded = day.load(&json.to_string()).unwrap_or_else(|err| panic!("{}", err));
+
+            assert_eq!(day.emit(loaded.as_ref()), json, "day {}", day.day);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Runs a day's generator and solvers on an input, timing each step.
+
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    allocations::{self, Allocations},
+    explain::{self, Note},
+    frames::Recorder,
+    limits::{self, Limits},
+    registry::Day,
+    Error, Result,
+};
+
+/// The answer to one part, how long it took to get there and what the solver
+/// noted along the way.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub year: u16,
+    pub day: u32,
+    pub part: u32,
+    pub answer: Result<String>,
+    pub generator: Duration,
+    pub solver: Duration,
+    pub notes: Vec<Note>,
+    /// What the generator allocated, if [allocations](crate::allocations) are counted.
+    pub generator_allocations: Option<Allocations>,
+    pub solver_allocations: Option<Allocations>,
+}
+
+/// Calls `f`, counting its allocations if the counting allocator is installed.
+fn count<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    let (result, allocations) = allocations::measure(f);
+
+    (result, Some(allocations).filter(|_| allocations::is_counting()))
+}
+
+/// Calls `f` under `limits`, turning a panic into an error so that one bad input
+/// cannot abort a batch.
+pub(crate) fn catch<T>(day: &Day, limits: &Limits, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| limits::enforce(limits, f)));
+
+    result.unwrap_or_else(|payload: Box<dyn Any + Send>| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown cause".to_string());
+
+        Err(Error::panicked(day.day, message))
+    })
+}
+
+/// Runs the generator for `day` once, then solves each of `parts` with its output.
+///
+/// If the generator fails, every part fails with its error. Panics count as failures,
+/// and so do solvers giving up once they exceed `limits`.
+pub fn run(day: &Day, input: &str, parts: &[u32], limits: &Limits) -> Vec<Outcome> {
+    solve(day, parts, limits, || day.generate(input))
+}
+
+/// Like [`run`], but with the generator's output given as JSON (see [`Day::load`]).
+pub fn run_json(day: &Day, json: &str, parts: &[u32], limits: &Limits) -> Vec<Outcome> {
+    solve(day, parts, limits, || day.load(json))
+}
+
+/// Gets the input with `generate`, then solves each of `parts` with it.
+fn solve(
+    day: &Day,
+    parts: &[u32],
+    limits: &Limits,
+    generate: impl FnOnce() -> Result<Box<dyn Any>>,
+) -> Vec<Outcome> {
+    let start = Instant::now();
+    let (generated, generator_allocations) = count(|| catch(day, limits, generate));
+    let generator = start.elapsed();
+
+    parts
+        .iter()
+        .map(|&part| {
+            let solver = day.solver(part).expect("no solver for part");
+
+            match &generated {
+                Ok(input) => {
+                    let start = Instant::now();
+                    let ((answer, notes), solver_allocations) = count(|| {
+                        explain::collect(|| catch(day, limits, || solver(input.as_ref())))
+                    });
+
+                    Outcome {
+                        year: day.year,
+                        day: day.day,
+                        part,
+                        answer,
+                        generator,
+                        solver: start.elapsed(),
+                        notes,
+                        generator_allocations,
+                        solver_allocations,
+                    }
+                }
+                Err(err) => Outcome {
+                    year: day.year,
+                    day: day.day,
+                    part,
+                    answer: Err(err.clone()),
+                    generator,
+                    solver: Duration::default(),
+                    notes: Vec::new(),
+                    generator_allocations,
+                    solver_allocations: None,
+                },
+            }
+        })
+        .collect()
+}
+
+/// Runs the generator for `day`, then solves `part` with its tracing solver, which
+/// records frames into `recorder`.
+pub fn run_traced(day: &Day, input: &str, part: u32, limits: &Limits, recorder: &mut Recorder) -> Outcome {
+    let tracer = day.tracer(part).expect("no tracer for part");
+
+    let start = Instant::now();
+    let (generated, generator_allocations) = count(|| catch(day, limits, || day.generate(input)));
+    let generator = start.elapsed();
+
+    let start = Instant::now();
+    let ((answer, notes), solver_allocations) = count(|| {
+        explain::collect(|| {
+            generated.and_then(|input| catch(day, limits, || tracer(input.as_ref(), recorder)))
+        })
+    });
+
+    Outcome {
+        year: day.year,
+        day: day.day,
+        part,
+        answer,
+        generator,
+        solver: start.elapsed(),
+        notes,
+        generator_allocations,
+        solver_allocations,
+    }
+}
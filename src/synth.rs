@@ -0,0 +1,129 @@
+//! Seeded generators of random, valid puzzle inputs for stress-testing the solvers.
+//!
+//! Each day has a `synthesize` function producing an input of roughly `size` items.
+//! Where the input is built around its answers (e.g. day 13's departure time is
+//! picked first and the buses placed to fit it), those answers come alongside.
+
+use std::ops::RangeInclusive;
+
+/// A small deterministic random number generator (SplitMix64), so that a seed
+/// always produces the same input.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (low, high) = range.into_inner();
+        assert!(low <= high, "empty range");
+
+        low + self.below((high - low) as u64 + 1) as i64
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+
+    /// A lowercase word of `len` letters.
+    pub fn word(&mut self, len: usize) -> String {
+        (0..len).map(|_| (b'a' + self.below(26) as u8) as char).collect()
+    }
+}
+
+/// A generated input, and the answer to each part if it is known by construction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Synthetic {
+    pub input: String,
+    pub answers: Vec<Option<String>>,
+}
+
+impl Synthetic {
+    /// An input whose answers are unknown.
+    pub fn new(input: String) -> Self {
+        Self {
+            input,
+            answers: Vec::new(),
+        }
+    }
+
+    pub fn with_answers(input: String, answers: Vec<Option<String>>) -> Self {
+        Self { input, answers }
+    }
+
+    /// The known answer to `part`.
+    pub fn answer(&self, part: u32) -> Option<&str> {
+        let index = part.checked_sub(1)? as usize;
+        self.answers.get(index)?.as_deref()
+    }
+}
+
+/// Joins `lines` into an input, one per line.
+pub fn lines<T: ToString>(lines: impl IntoIterator<Item = T>) -> String {
+    lines
+        .into_iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::registry;
+
+    #[test]
+    fn test_known_answers() {
+        for day in registry::days() {
+            for seed in 0..3 {
+                let synthetic = day.synthesize(seed, 20);
+                let input = day
+                    .generate(&synthetic.input)
+                    .unwrap_or_else(|err| panic!("seed {}: {}", seed, err));
+
+                for part in day.parts() {
+                    if let Some(answer) = synthetic.answer(part) {
+                        let solver = day.solver(part).unwrap();
+
+                        let message = format!("day {} part {} seed {}", day.day, part, seed);
+                        assert_eq!(solver(input.as_ref()).as_deref(), Ok(answer), "{}", message);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,482 @@
+//! Day 1: Report Repair.
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::{iproduct, Itertools};
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fmt,
+    io::BufRead,
+    iter::{self, FromIterator},
+};
+
+use crate::{
+    error::{Error, Line, Result},
+    explain,
+    input::{Input, BOM},
+    limits::Checkpoint,
+    synth::{self, Rng, Synthetic},
+};
+
+/// The sum the expense report entries must add up to.
+pub const TARGET: i32 = 2020;
+
+#[aoc_generator(day1)]
+pub fn parse(input: &str) -> Result<Vec<i32>> {
+    Input::new(1, input).lines().map(|line| line.parse()).collect()
+}
+
+#[aoc(day1, part1)]
+pub fn part1(nums: &[i32]) -> Result<i128> {
+    solve(nums, 2)
+}
+
+#[aoc(day1, part2)]
+pub fn part2(nums: &[i32]) -> Result<i128> {
+    solve(nums, 3)
+}
+
+fn solve(nums: &[i32], k: usize) -> Result<i128> {
+    let combination = k_sum(nums, k, TARGET)?
+        .ok_or_else(|| Error::gave_up(1, format!("no {} entries add up to {}", k, TARGET)))?;
+
+    explain::note(1, "entries", || &combination);
+
+    Ok(combination.product())
+}
+
+/// Entries of an expense report which add up to a target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Combination {
+    /// Positions of the entries in the report, in ascending order.
+    pub indices: Vec<usize>,
+    /// The entries at those positions.
+    pub values: Vec<i32>,
+}
+
+impl Combination {
+    pub fn sum(&self) -> i64 {
+        self.values.iter().map(|&value| i64::from(value)).sum()
+    }
+
+    pub fn product(&self) -> i128 {
+        self.values.iter().map(|&value| i128::from(value)).product()
+    }
+}
+
+impl fmt::Display for Combination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.values.iter().join(" + "), self.sum())
+    }
+}
+
+/// Finds `k` entries of `nums` which add up to `target`. Entries are distinct by
+/// position, so a value can be used as often as it occurs.
+pub fn k_sum(nums: &[i32], k: usize, target: i32) -> Result<Option<Combination>> {
+    let mut found = None;
+
+    search(nums, k, target, |combination| {
+        found = Some(combination);
+        false
+    })?;
+
+    Ok(found)
+}
+
+/// Finds every combination of `k` entries of `nums` which add up to `target`, as
+/// [`k_sum`] would, ordered by their indices.
+pub fn k_sum_all(nums: &[i32], k: usize, target: i32) -> Result<Vec<Combination>> {
+    let mut found = Vec::new();
+
+    search(nums, k, target, |combination| {
+        found.push(combination);
+        true
+    })?;
+
+    found.sort_by(|a, b| a.indices.cmp(&b.indices));
+    Ok(found)
+}
+
+/// Calls `visit` with the combinations of `k` entries adding up to `target` until it
+/// returns false. Picks entries in ascending order of value, skipping the rest of a
+/// branch once its smallest possible sum is too large.
+fn search(nums: &[i32], k: usize, target: i32, mut visit: impl FnMut(Combination) -> bool) -> Result<()> {
+    let mut sorted: Vec<(i32, usize)> = nums.iter().copied().zip(0..).collect();
+    sorted.sort_unstable();
+
+    let mut checkpoint = Checkpoint::new(1);
+    let mut picked = Vec::with_capacity(k);
+
+    if k > 0 && k <= nums.len() {
+        search_from(&sorted, k, i64::from(target), &mut picked, &mut visit, &mut checkpoint)?;
+    }
+
+    Ok(())
+}
+
+/// Picks `k` more entries from `sorted` adding up to `remaining`. Returns whether to
+/// keep searching.
+fn search_from(
+    sorted: &[(i32, usize)],
+    k: usize,
+    remaining: i64,
+    picked: &mut Vec<(i32, usize)>,
+    visit: &mut impl FnMut(Combination) -> bool,
+    checkpoint: &mut Checkpoint,
+) -> Result<bool> {
+    checkpoint.tick()?;
+
+    let sum = |entries: &[(i32, usize)]| entries.iter().map(|&(value, _)| i64::from(value)).sum::<i64>();
+
+    if k == 1 {
+        // Each entry equal to `remaining` completes a combination.
+        let from = sorted.partition_point(|&(value, _)| i64::from(value) < remaining);
+        let to = sorted.partition_point(|&(value, _)| i64::from(value) <= remaining);
+
+        for &entry in &sorted[from..to] {
+            let mut entries = picked.clone();
+            entries.push(entry);
+            entries.sort_unstable_by_key(|&(_, index)| index);
+
+            let (values, indices) = entries.into_iter().unzip();
+
+            if !visit(Combination { indices, values }) {
+                return Ok(false);
+            }
+        }
+
+        return Ok(true);
+    }
+
+    let largest = sum(&sorted[sorted.len() + 1 - k..]);
+
+    for i in 0..=sorted.len() - k {
+        let value = i64::from(sorted[i].0);
+
+        if sum(&sorted[i..i + k]) > remaining {
+            break;
+        } else if value + largest < remaining {
+            continue;
+        }
+
+        picked.push(sorted[i]);
+        let more = search_from(&sorted[i + 1..], k - 1, remaining - value, picked, visit, checkpoint)?;
+        picked.pop();
+
+        if !more {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// How many entries of an expense report have each value. This is all a search
+/// by value needs, and it stays as small as the number of distinct values however
+/// long the report is.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tally {
+    /// The distinct values in ascending order, with how many entries have them.
+    counts: Vec<(i32, u64)>,
+}
+
+impl Tally {
+    /// Tallies the entries of a report one line at a time, so that it never needs to
+    /// be held in memory.
+    pub fn read(reader: impl BufRead) -> Result<Self> {
+        let mut counts = HashMap::new();
+        let mut blank = None;
+
+        for (line, number) in reader.lines().zip(1..) {
+            let line = line.map_err(|err| Error::parse(1, number, 1, "", format!("cannot read ({})", err)))?;
+            // Like `Input`, which only drops a byte order mark at the very start.
+            let text = match number {
+                1 => line.strip_prefix(BOM).unwrap_or(&line),
+                _ => &line,
+            };
+            let text = text.trim_end();
+
+            // Like the generator, which only trims trailing blank lines.
+            if text.is_empty() {
+                blank = blank.or(Some(number));
+                continue;
+            } else if let Some(number) = blank {
+                return Err(Error::parse(1, number, 1, "", "unexpected blank line"));
+            }
+
+            let num: i32 = Line { day: 1, number, text }.parse()?;
+            *counts.entry(num).or_insert(0) += 1;
+        }
+
+        Ok(Self::from_counts(counts))
+    }
+
+    fn from_counts(counts: HashMap<i32, u64>) -> Self {
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_unstable();
+
+        Self { counts }
+    }
+
+    /// Lazily finds each set of values adding up to `target` which `k` entries can
+    /// have, in ascending order of values.
+    pub fn sums(&self, k: usize, target: i32) -> Sums<'_> {
+        Sums {
+            counts: &self.counts,
+            k,
+            target: i64::from(target),
+            picked: Vec::with_capacity(k),
+            sum: 0,
+            started: false,
+            done: k == 0,
+            checkpoint: Checkpoint::new(1),
+        }
+    }
+
+    /// How many combinations of `k` entries add up to `target`, counting entries
+    /// apart by position like [`k_sum_all`] does, but without listing them.
+    pub fn count(&self, k: usize, target: i32) -> Result<u128> {
+        self.sums(k, target).map(|group| Ok(group?.count)).sum()
+    }
+}
+
+impl FromIterator<i32> for Tally {
+    fn from_iter<I: IntoIterator<Item = i32>>(nums: I) -> Self {
+        let mut counts = HashMap::new();
+
+        for num in nums {
+            *counts.entry(num).or_insert(0) += 1;
+        }
+
+        Self::from_counts(counts)
+    }
+}
+
+/// Values adding up to a target, in ascending order, and how many combinations of
+/// entries have them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Group {
+    pub values: Vec<i32>,
+    pub count: u128,
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sum: i64 = self.values.iter().map(|&value| i64::from(value)).sum();
+        write!(f, "{} = {}", self.values.iter().join(" + "), sum)
+    }
+}
+
+/// The [`Group`]s of a [`Tally`] adding up to a target, found one at a time.
+///
+/// The values picked so far are indices into the tally in ascending order, at most
+/// as many of each as there are entries with it. Once `k - 1` are picked, only one
+/// value can complete the sum.
+pub struct Sums<'a> {
+    counts: &'a [(i32, u64)],
+    k: usize,
+    target: i64,
+    picked: Vec<usize>,
+    sum: i64,
+    started: bool,
+    done: bool,
+    checkpoint: Checkpoint,
+}
+
+impl Sums<'_> {
+    fn value(&self, i: usize) -> i64 {
+        i64::from(self.counts[i].0)
+    }
+
+    /// How many of the picked values are the one at `i`.
+    fn uses(&self, i: usize) -> u64 {
+        self.picked.iter().rev().take_while(|&&j| j == i).count() as u64
+    }
+
+    /// Moves on to the next `k - 1` values which might be completed, skipping the
+    /// rest of a branch once its smallest possible sum is too large.
+    fn advance(&mut self) -> Result<bool> {
+        let mut next = self.started;
+        self.started = true;
+
+        loop {
+            self.checkpoint.tick()?;
+
+            let i = if next {
+                match self.picked.pop() {
+                    Some(i) => {
+                        self.sum -= self.value(i);
+                        i + 1
+                    }
+                    None => return Ok(false),
+                }
+            } else if self.picked.len() < self.k - 1 {
+                self.picked.last().copied().unwrap_or(0)
+            } else {
+                return Ok(true);
+            };
+
+            if i == self.counts.len() {
+                next = true;
+                continue;
+            }
+
+            let value = self.value(i);
+            let left = (self.k - self.picked.len() - 1) as i64;
+            let largest = self.value(self.counts.len() - 1);
+
+            if self.sum + value * (left + 1) > self.target {
+                // Every later value is too large as well.
+                next = true;
+            } else if self.uses(i) == self.counts[i].1 || self.sum + value + largest * left < self.target {
+                self.picked.push(i);
+                self.sum += value;
+                next = true;
+            } else {
+                self.picked.push(i);
+                self.sum += value;
+                next = false;
+            }
+        }
+    }
+
+    /// The group the picked values make with the one value completing them, if
+    /// there is such a value and entries enough to have it.
+    fn complete(&self) -> Option<Group> {
+        let from = self.picked.last().copied().unwrap_or(0);
+        let last = i32::try_from(self.target - self.sum).ok()?;
+        let i = from + self.counts[from..].binary_search_by_key(&last, |&(value, _)| value).ok()?;
+
+        if self.uses(i) == self.counts[i].1 {
+            return None;
+        }
+
+        let picked: Vec<usize> = self.picked.iter().copied().chain(iter::once(i)).collect();
+
+        let runs = picked.iter().group_by(|&&i| i);
+        let count = runs
+            .into_iter()
+            .map(|(i, run)| binomial(self.counts[i].1, run.count() as u64))
+            .product();
+
+        let values = picked.iter().map(|&i| self.counts[i].0).collect();
+
+        Some(Group { values, count })
+    }
+}
+
+impl Iterator for Sums<'_> {
+    type Item = Result<Group>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.advance() {
+                Ok(true) => {
+                    if let Some(group) = self.complete() {
+                        return Some(Ok(group));
+                    }
+                }
+                Ok(false) => self.done = true,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// How many ways there are to choose `k` of `n`.
+fn binomial(n: u64, k: u64) -> u128 {
+    (0..k).fold(1, |ways, i| ways * u128::from(n - i) / u128::from(i + 1))
+}
+
+/// Expense report entries with exactly one pair and one triple adding up to [`TARGET`].
+///
+/// The planted entries are small, apart from the pair's larger half. All others
+/// are too large to combine with anything but small entries, and never the
+/// value which would complete a pair or triple with them.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let (small, b) = loop {
+        let a = rng.range(1..=1009) as i32;
+        let c = rng.range(300..=900) as i32;
+        let d = rng.range(300..=900) as i32;
+        let e = TARGET - c - d;
+
+        if (1..=1009).contains(&e) && [a, c, d, e].iter().unique().count() == 4 {
+            let planted = [a, TARGET - a, c, d, e];
+
+            let pairs = planted
+                .iter()
+                .tuple_combinations()
+                .filter(|&(x, y)| x + y == TARGET)
+                .count();
+
+            // Counting ordered triples, and ones which reuse an entry, so that the
+            // planted triple is the only one whichever way it is searched for.
+            let triples = iproduct!(&planted, &planted, &planted)
+                .filter(|&(x, y, z)| x + y + z == TARGET)
+                .count();
+
+            if pairs == 1 && triples == 6 && [c, d, e].iter().unique().count() == 3 {
+                break ([a, c, d, e], TARGET - a);
+            }
+        }
+    };
+
+    let excluded: HashSet<i32> = iproduct!(&small, &small)
+        .map(|(x, y)| TARGET - x - y)
+        .chain(small.iter().map(|x| TARGET - x))
+        .collect();
+
+    let mut fillers: Vec<i32> = (1011..TARGET).filter(|n| !excluded.contains(n)).collect();
+    rng.shuffle(&mut fillers);
+    fillers.truncate(size.saturating_sub(5));
+
+    let mut nums: Vec<i32> = small.iter().copied().chain(std::iter::once(b)).chain(fillers).collect();
+    rng.shuffle(&mut nums);
+
+    let [a, c, d, e] = small;
+
+    Synthetic::with_answers(
+        synth::lines(nums),
+        vec![Some((a * b).to_string()), Some((c * d * e).to_string())],
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_k_sum_duplicates() {
+        let nums = [1010, 5, 1010, 1010, 2015];
+
+        let pairs: Vec<_> = k_sum_all(&nums, 2, TARGET).unwrap().into_iter().map(|c| c.indices).collect();
+        assert_eq!(pairs, vec![vec![0, 2], vec![0, 3], vec![1, 4], vec![2, 3]]);
+
+        assert_eq!(k_sum(&[1010], 2, TARGET).unwrap(), None);
+        assert_eq!(k_sum_all(&nums, 3, 3030).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_tally() {
+        let report = "\u{feff}1010\n5\n1010\n1010\n2015\n2020\n0\n0\n";
+        let tally = Tally::read(report.as_bytes()).unwrap();
+        assert!(Tally::read("1\n\u{feff}2".as_bytes()).is_err());
+        let nums = parse(report).unwrap();
+
+        let groups: Vec<_> = tally.sums(3, TARGET).map(|group| group.unwrap().to_string()).collect();
+        assert_eq!(groups, vec!["0 + 0 + 2020 = 2020", "0 + 5 + 2015 = 2020", "0 + 1010 + 1010 = 2020"]);
+
+        for k in 1..=5 {
+            for &target in &[0, 2020, 3030, 4040] {
+                let count = k_sum_all(&nums, k, target).unwrap().len() as u128;
+                assert_eq!(tally.count(k, target).unwrap(), count, "{} entries adding up to {}", k, target);
+            }
+        }
+    }
+}
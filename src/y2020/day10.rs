@@ -0,0 +1,173 @@
+//! Day 10: Adapter Array.
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+
+use crate::{
+    error::Result,
+    input::Input,
+    synth::{self, Rng, Synthetic},
+};
+
+#[aoc_generator(day10)]
+pub fn parse(input: &str) -> Result<Vec<u64>> {
+    let input = Input::new(10, input);
+    let mut adapters = input
+        .lines()
+        .map(|line| Ok((line.parse()?, line)))
+        .collect::<Result<Vec<(u64, _)>>>()?;
+
+    // Stable, so a repeated joltage is reported at its second line.
+    adapters.sort_by_key(|&(jolts, _)| jolts);
+
+    let mut previous = 0;
+
+    for (jolts, line) in &adapters {
+        let reason = match jolts - previous {
+            1..=3 => {
+                previous = *jolts;
+                continue;
+            }
+            0 if previous == 0 => "adapters must be rated above 0 jolts",
+            0 => "another adapter has the same joltage",
+            _ => "no adapter is within 3 jolts below this one",
+        };
+
+        return Err(line.error(line.text, reason));
+    }
+
+    Ok(adapters.into_iter().map(|(jolts, _)| jolts).collect())
+}
+
+/// Sorts the adapters and adds the outlet (0) and the device (max + 3).
+pub fn sorted_with_edges(adapters: &[u64]) -> Vec<u64> {
+    let mut result: Vec<u64> = adapters.to_vec();
+
+    result.push(0);
+    result.sort_unstable();
+    result.push(result.last().unwrap() + 3);
+
+    result
+}
+
+#[aoc(day10, part1)]
+pub fn part1(adapters: &[u64]) -> u64 {
+    let (diff1, diff3) = sorted_with_edges(adapters)
+        .iter()
+        .tuple_windows()
+        .fold((0u64, 0u64), |(diff1, diff3), (a, b)| {
+            match b - a {
+                1 => (diff1 + 1, diff3),
+                3 => (diff1, diff3 + 1),
+                _ => (diff1, diff3)
+            }
+        });
+
+    diff1 * diff3
+}
+
+#[aoc(day10, part2)]
+pub fn part2(adapters: &[u64]) -> u64 {
+    sorted_with_edges(adapters)
+        .iter()
+        .tuple_windows()
+        .fold((1u64, 0u64, 0u64), |(diff1, diff2, diff3), (a, b)| {
+            match b - a {
+                1 => (diff1 + diff2 + diff3, diff1, diff2),
+                2 => (diff1 + diff2, 0, diff1),
+                3 => (diff1, 0, 0),
+                _ => unreachable!("the generator rejects gaps outside 1 to 3 jolts")
+            }
+        })
+        .0
+}
+
+/// [`part2`] without folding over the differences: counts the ways to reach each
+/// adapter from every adapter within three jolts below it.
+pub fn part2_reference(adapters: &[u64]) -> u64 {
+    let jolts = sorted_with_edges(adapters);
+    let mut ways = vec![1u64; jolts.len()];
+
+    for i in 1..jolts.len() {
+        ways[i] = (0..i).filter(|&j| jolts[i] - jolts[j] <= 3).map(|j| ways[j]).sum();
+    }
+
+    ways[jolts.len() - 1]
+}
+
+/// The ways to keep a subset of the adapters between two fixed ones, where `diffs`
+/// are the differences between consecutive adapters, with no gap over three jolts.
+fn run_arrangements(diffs: &[u64]) -> u64 {
+    let inner = diffs.len().saturating_sub(1);
+
+    (0..1u32 << inner)
+        .filter(|&dropped| {
+            let mut gap = 0;
+
+            diffs.iter().enumerate().all(|(i, &diff)| {
+                gap += diff;
+
+                if i < inner && dropped >> i & 1 == 1 {
+                    return true;
+                }
+
+                std::mem::replace(&mut gap, 0) <= 3
+            })
+        })
+        .count() as u64
+}
+
+/// About `size` adapters in runs of up to four one or two jolts apart, separated by
+/// three jolt gaps. Larger sizes are capped to keep the arrangements within a `u64`.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let len = size.clamp(1, 100);
+    let mut adapters = Vec::with_capacity(len);
+    let mut jolts = 0;
+    let mut run = 0;
+
+    while adapters.len() < len {
+        let step = match run < 4 && rng.chance(0.7) {
+            true if rng.chance(0.2) => 2,
+            true => 1,
+            false => 3,
+        };
+        run = if step < 3 { run + 1 } else { 0 };
+
+        jolts += step;
+        adapters.push(jolts);
+    }
+
+    let diffs: Vec<u64> = sorted_with_edges(&adapters)
+        .iter()
+        .tuple_windows()
+        .map(|(a, b)| b - a)
+        .collect();
+
+    let count = |diff| diffs.iter().filter(|&&d| d == diff).count() as u64;
+    let arrangements: u64 = diffs
+        .split(|&d| d == 3)
+        .map(run_arrangements)
+        .product();
+
+    rng.shuffle(&mut adapters);
+
+    Synthetic::with_answers(
+        synth::lines(adapters),
+        vec![Some((count(1) * count(3)).to_string()), Some(arrangements.to_string())],
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_rejects_unchainable_adapters() {
+        assert_eq!(parse("3\n1\n2").unwrap(), vec![1, 2, 3]);
+
+        for (input, line) in &[("0\n1", 1), ("1\n2\n1", 3), ("1\n5", 2), ("4", 1)] {
+            let error = parse(input).unwrap_err().to_string();
+            assert!(error.contains(&format!("line {}", line)), "{}: {}", input, error);
+        }
+    }
+}
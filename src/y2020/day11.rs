@@ -0,0 +1,191 @@
+//! Day 11: Seating System.
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+use crate::{
+    automaton::{Automaton, Bounded, Mode, Neighborhood},
+    error::Result,
+    frames::{Frame, Recorder},
+    grid::Grid,
+    input::Input,
+    limits::Checkpoint,
+    synth::{Rng, Synthetic},
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tile {
+    Floor,
+    Occupied,
+    Empty,
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Tile::Floor => '.',
+            Tile::Empty => 'L',
+            Tile::Occupied => '#',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+/// An empty seat with no occupied neighbors fills, and an occupied seat empties when 4 or more
+/// neighbors are occupied.
+pub const RULE_P1: &str = "B0/S0123";
+/// Like [`RULE_P1`], but occupied seats tolerate 4 visible neighbors.
+pub const RULE_P2: &str = "B0/S01234";
+
+/// The waiting area's seat layout.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Map {
+    tiles: Grid<Tile>,
+}
+
+impl Map {
+    pub fn new(tiles: Grid<Tile>) -> Map {
+        Map { tiles }
+    }
+
+    pub fn tiles(&self) -> &Grid<Tile> {
+        &self.tiles
+    }
+
+    /// An automaton in which seats are cells, and occupied seats are live.
+    pub fn automaton(&self, neighborhood: Neighborhood, rule: &str) -> Automaton<Bounded> {
+        let seats = self.tiles.map(|&tile| tile != Tile::Floor);
+        let occupied = self
+            .tiles
+            .positions()
+            .filter(|&pos| self.tiles[pos] == Tile::Occupied);
+
+        Automaton::new(
+            Bounded::new(seats, neighborhood),
+            rule.parse().expect("invalid rule"),
+            occupied,
+        )
+    }
+
+    /// Runs until nobody moves, and counts the occupied seats. Gives up if the solver
+    /// runs out of budget first, as it does when seats keep flipping forever.
+    pub fn settle(
+        &self,
+        neighborhood: Neighborhood,
+        rule: &str,
+        recorder: &mut Recorder,
+    ) -> Result<usize> {
+        let mut automaton = self.automaton(neighborhood, rule);
+        let mut checkpoint = Checkpoint::new(11).with_stride(1);
+
+        automaton.run_checked(Mode::UntilStable, &mut checkpoint, |automaton| {
+            recorder.record(|| self.render(automaton))
+        })?;
+
+        Ok(automaton.population())
+    }
+
+    /// Draws floor as dark, empty seats as dim and occupied seats as bright.
+    pub fn render(&self, automaton: &Automaton<Bounded>) -> Frame {
+        let mut frame = self.tiles.map(|&tile| if tile == Tile::Floor { 0 } else { 80 });
+
+        for &pos in automaton.live() {
+            frame[pos] = 255;
+        }
+
+        frame
+    }
+}
+
+#[aoc_generator(day11)]
+pub fn parse(input: &str) -> Result<Map> {
+    let tiles = Input::new(11, input).grid("one of '.', 'L' or '#'", |c| match c {
+        '.' => Some(Tile::Floor),
+        'L' => Some(Tile::Empty),
+        '#' => Some(Tile::Occupied),
+        _ => None,
+    })?;
+
+    Ok(Map::new(tiles))
+}
+
+#[aoc(day11, part1)]
+pub fn part1(map: &Map) -> Result<usize> {
+    part1_traced(map, &mut Recorder::disabled())
+}
+
+pub fn part1_traced(map: &Map, recorder: &mut Recorder) -> Result<usize> {
+    map.settle(Neighborhood::Adjacent, RULE_P1, recorder)
+}
+
+#[aoc(day11, part2)]
+pub fn part2(map: &Map) -> Result<usize> {
+    part2_traced(map, &mut Recorder::disabled())
+}
+
+pub fn part2_traced(map: &Map, recorder: &mut Recorder) -> Result<usize> {
+    map.settle(Neighborhood::LineOfSight, RULE_P2, recorder)
+}
+
+/// Runs until nobody moves. Fails with the seats which flip back and forth forever,
+/// or with none if it did not settle within `limit` generations.
+fn settles(
+    map: &Map,
+    neighborhood: Neighborhood,
+    rule: &str,
+    limit: usize,
+) -> std::result::Result<(), Vec<(usize, usize)>> {
+    let mut automaton = map.automaton(neighborhood, rule);
+    let mut previous = automaton.live().clone();
+
+    for _ in 0..limit {
+        let before = automaton.live().clone();
+
+        if !automaton.step() {
+            return Ok(());
+        }
+
+        if *automaton.live() == previous {
+            return Err(automaton.live().symmetric_difference(&before).copied().collect());
+        }
+
+        previous = before;
+    }
+
+    Err(Vec::new())
+}
+
+/// A square waiting area `size` seats wide, with a fifth of it floor. Some layouts
+/// never settle, with seats flipping back and forth forever, so those seats are
+/// turned into floor.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let side = size.max(1);
+    let random_tiles = |rng: &mut Rng| {
+        let tiles = (0..side * side).map(|_| if rng.chance(0.2) { Tile::Floor } else { Tile::Empty });
+        Grid::new(side, side, tiles.collect())
+    };
+
+    let mut tiles = random_tiles(rng);
+
+    loop {
+        let map = Map::new(tiles.clone());
+        let limit = 10 * side + 100;
+
+        match settles(&map, Neighborhood::Adjacent, RULE_P1, limit)
+            .and_then(|_| settles(&map, Neighborhood::LineOfSight, RULE_P2, limit))
+        {
+            Ok(()) => return Synthetic::new(tiles.to_string().trim_end().to_string()),
+            Err(flipping) if !flipping.is_empty() => {
+                for pos in flipping {
+                    tiles[pos] = Tile::Floor;
+                }
+            }
+            Err(_) => tiles = random_tiles(rng),
+        }
+    }
+}
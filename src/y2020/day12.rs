@@ -1,9 +1,19 @@
+//! Day 12: Rain Risk.
+
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use parse_display::{Display, FromStr, ParseError};
+use parse_display::{Display, FromStr};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Result,
+    input::Input,
+    synth::{self, Rng, Synthetic},
+};
 
-#[derive(Display, FromStr, Copy, Clone)]
-enum Action {
+#[derive(Display, FromStr, Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
     #[display("N{0}")]
     North(i64),
     #[display("S{0}")]
@@ -20,7 +30,8 @@ enum Action {
     Forward(i64),
 }
 
-trait Navigate {
+/// A ship which can follow navigation instructions.
+pub trait Navigate {
     fn shift(&mut self, x: i64, y: i64);
     fn rotate(&mut self, degrees: i64);
     fn forward(&mut self, units: i64);
@@ -40,13 +51,14 @@ trait Navigate {
     }
 }
 
-struct SimpleShip {
-    position: (i64, i64),
-    direction: i64,
+/// A ship which moves itself in the direction it faces.
+pub struct SimpleShip {
+    pub position: (i64, i64),
+    pub direction: i64,
 }
 
 impl SimpleShip {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             position: (0, 0),
             direction: 90,
@@ -54,6 +66,12 @@ impl SimpleShip {
     }
 }
 
+impl Default for SimpleShip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Navigate for SimpleShip {
     fn shift(&mut self, x: i64, y: i64) {
         self.position.0 += x;
@@ -75,13 +93,14 @@ impl Navigate for SimpleShip {
     }
 }
 
-struct WaypointShip {
-    position: (i64, i64),
-    waypoint: (i64, i64),
+/// A ship which moves towards a waypoint relative to itself.
+pub struct WaypointShip {
+    pub position: (i64, i64),
+    pub waypoint: (i64, i64),
 }
 
 impl WaypointShip {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             position: (0, 0),
             waypoint: (10, 1),
@@ -89,6 +108,12 @@ impl WaypointShip {
     }
 }
 
+impl Default for WaypointShip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Navigate for WaypointShip {
     fn shift(&mut self, x: i64, y: i64) {
         self.waypoint.0 += x;
@@ -112,17 +137,20 @@ impl Navigate for WaypointShip {
     }
 }
 
-fn manhattan_distance((x, y): (i64, i64)) -> i64 {
+pub fn manhattan_distance((x, y): (i64, i64)) -> i64 {
     x.abs() + y.abs()
 }
 
 #[aoc_generator(day12)]
-fn parse(input: &str) -> Result<Vec<Action>, ParseError> {
-    input.lines().map(str::parse).collect()
+pub fn parse(input: &str) -> Result<Vec<Action>> {
+    Input::new(12, input)
+        .lines()
+        .map(|line| line.parse_format("N|S|E|W|L|R|F{value}"))
+        .collect()
 }
 
 #[aoc(day12, part1)]
-fn part1(actions: &[Action]) -> i64 {
+pub fn part1(actions: &[Action]) -> i64 {
     let mut ship = SimpleShip::new();
 
     ship.navigate(actions);
@@ -131,10 +159,29 @@ fn part1(actions: &[Action]) -> i64 {
 }
 
 #[aoc(day12, part2)]
-fn part2(actions: &[Action]) -> i64 {
+pub fn part2(actions: &[Action]) -> i64 {
     let mut ship = WaypointShip::new();
 
     ship.navigate(actions);
 
     manhattan_distance(ship.position)
 }
+
+/// `size` random navigation instructions, turning only by multiples of 90 degrees.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let actions = (0..size.max(1)).map(|_| {
+        let value = rng.range(1..=100);
+
+        match rng.below(7) {
+            0 => Action::North(value),
+            1 => Action::South(value),
+            2 => Action::East(value),
+            3 => Action::West(value),
+            4 => Action::Left(90 * rng.range(1..=3)),
+            5 => Action::Right(90 * rng.range(1..=3)),
+            _ => Action::Forward(value),
+        }
+    });
+
+    Synthetic::new(synth::lines(actions.collect::<Vec<_>>()))
+}
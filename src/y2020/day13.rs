@@ -0,0 +1,187 @@
+//! Day 13: Shuttle Search.
+
+use std::convert::TryFrom;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::{
+    error::{Error, Result},
+    input::Input,
+    synth::{Rng, Synthetic},
+};
+
+/// The earliest departure time and the bus IDs, `None` for an `x`.
+pub type Notes = (u64, Vec<Option<u64>>);
+
+#[aoc_generator(day13)]
+pub fn parse(input: &str) -> Result<Notes> {
+    let input = Input::new(13, input);
+    let mut lines = input.lines();
+
+    let arrival_time = lines
+        .next()
+        .ok_or_else(|| Error::unexpected_end(13, 1, "the earliest departure time"))?
+        .parse()?;
+
+    let line = lines
+        .next()
+        .ok_or_else(|| Error::unexpected_end(13, 2, "the bus IDs"))?;
+
+    let busses: Vec<_> = line
+        .text
+        .split(',')
+        .map(|bus| match bus {
+            "x" => Ok(None),
+            _ => match line.parse_token(bus)? {
+                0 => Err(line.error(bus, "bus IDs must be positive")),
+                id => Ok(Some(id)),
+            },
+        })
+        .collect::<Result<_>>()?;
+
+    if busses.iter().all(Option::is_none) {
+        return Err(line.error(line.text, "expected at least one bus ID"));
+    }
+
+    Ok((arrival_time, busses))
+}
+
+#[aoc(day13, part1)]
+pub fn part1((arrival_time, busses): &Notes) -> Result<u64> {
+    if busses.iter().all(Option::is_none) {
+        return Err(Error::gave_up(13, "there are no buses"));
+    }
+
+    let (departure_time, bus_id) = busses
+        .iter()
+        .flatten()
+        .filter_map(|&id| Some((arrival_time.div_ceil(id).checked_mul(id)?, id)))
+        .min_by_key(|&(time, _)| time)
+        .ok_or_else(|| Error::gave_up(13, "no bus departs at a time which fits in 64 bits"))?;
+
+    bus_id
+        .checked_mul(departure_time - arrival_time)
+        .ok_or_else(|| Error::gave_up(13, "the answer does not fit in 64 bits"))
+}
+
+/// The inverse of `a` modulo `n`, if `a` and `n` are coprime.
+pub fn mod_inv(a: i64, n: i64) -> Option<i64> {
+    let mut r = (n, a);
+    let mut t = (0, 1);
+
+    while r.1 != 0 {
+        let quot = r.0 / r.1;
+        r = (r.1, r.0 - quot * r.1);
+        t = (t.1, t.0 - quot * t.1);
+    }
+
+    if r.0 > 1 {
+        None
+    } else if t.0 < 0 {
+        Some(t.0 + n)
+    } else {
+        Some(t.0)
+    }
+}
+
+/// Solves the system `x = remainders[i] (mod mods[i])` with the Chinese
+/// remainder theorem, if the moduli are pairwise coprime and their product fits in
+/// an `i64`.
+pub fn crt(mods: &[i64], remainders: &[i64]) -> Option<i64> {
+    assert_eq!(mods.len(), remainders.len());
+
+    let prod = mods.iter().try_fold(1i64, |prod, &m| prod.checked_mul(m))?;
+    let mut sum = 0;
+
+    // Terms are reduced modulo `prod` as they go, in `i128` where they would overflow.
+    for (&m, &r) in mods.iter().zip(remainders) {
+        let y = prod / m;
+        let term = i128::from(r) * i128::from(y) % i128::from(prod) * i128::from(mod_inv(y, m)?);
+        sum = (sum + term) % i128::from(prod);
+    }
+
+    i64::try_from(sum.rem_euclid(i128::from(prod))).ok()
+}
+
+#[aoc(day13, part2)]
+pub fn part2((_, busses): &Notes) -> Result<i64> {
+    let mut mods = Vec::new();
+    let mut remainders = Vec::new();
+
+    for (i, bus) in busses.iter().enumerate() {
+        if let &Some(id) = bus {
+            mods.push(id as i64);
+            remainders.push(-(i as i64));
+        }
+    }
+
+    crt(&mods, &remainders).ok_or_else(|| {
+        Error::gave_up(13, "the bus IDs must be pairwise coprime, with a product which fits in 64 bits")
+    })
+}
+
+/// Notes with up to `size` buses of distinct prime IDs, placed so that the earliest
+/// timestamp of part 2 is picked in advance. Their product is kept below 10^14.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let primes: Vec<u64> = (7..500u64)
+        .filter(|&n| (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .collect();
+
+    let mut ids: Vec<u64> = Vec::new();
+    let mut product = 1u64;
+
+    while ids.len() < size.max(2) {
+        let candidates: Vec<u64> = primes
+            .iter()
+            .copied()
+            .filter(|&p| !ids.contains(&p) && product * p <= 100_000_000_000_000)
+            .collect();
+
+        if candidates.is_empty() {
+            break;
+        }
+
+        let id = *rng.choose(&candidates);
+        product *= id;
+        ids.push(id);
+    }
+
+    let timestamp = ids[0] * rng.below(product / ids[0]);
+    let mut offsets: Vec<Option<u64>> = Vec::new();
+
+    for &id in &ids {
+        let mut offset = (id - timestamp % id) % id;
+
+        if !offsets.is_empty() {
+            offset += id * rng.below(3);
+        }
+
+        while offsets.get(offset as usize).is_some_and(Option::is_some) {
+            offset += id;
+        }
+
+        if offsets.len() <= offset as usize {
+            offsets.resize(offset as usize + 1, None);
+        }
+
+        offsets[offset as usize] = Some(id);
+    }
+
+    let arrival_time = rng.range(100_000..=10_000_000) as u64;
+    let (departure_time, id) = offsets
+        .iter()
+        .flatten()
+        .map(|&id| (arrival_time.div_ceil(id) * id, id))
+        .fold((u64::MAX, 0), |best, next| if next.0 < best.0 { next } else { best });
+
+    let busses = offsets
+        .iter()
+        .map(|bus| bus.map_or("x".to_string(), |id| id.to_string()))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    Synthetic::with_answers(
+        format!("{}\n{}", arrival_time, busses),
+        vec![Some((id * (departure_time - arrival_time)).to_string()), Some(timestamp.to_string())],
+    )
+}
@@ -0,0 +1,203 @@
+//! Day 14: Docking Data.
+
+use std::collections::HashMap;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use parse_display::{Display, FromStr};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Result,
+    input::Input,
+    limits::Checkpoint,
+    synth::{self, Rng, Synthetic},
+};
+
+#[derive(Display, FromStr, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Instruction {
+    #[display("mask = {0}")]
+    Mask(String),
+    #[display("mem[{0}] = {1}")]
+    Assignment(u64, u64),
+}
+
+#[aoc_generator(day14)]
+pub fn parse(input: &str) -> Result<Vec<Instruction>> {
+    Input::new(14, input)
+        .lines()
+        .map(|line| {
+            let instruction = line.parse_format("mask = {mask}\" or \"mem[{address}] = {value}")?;
+
+            if let Instruction::Mask(mask) = &instruction {
+                let token = &line.text[line.text.len() - mask.len()..];
+
+                if mask.len() != 36 {
+                    return Err(line.error(token, "expected a 36 bit mask"));
+                }
+
+                if let Some(i) = token.find(|c| !"01X".contains(c)) {
+                    return Err(line.error(&token[i..], "expected only '0', '1' or 'X'"));
+                }
+            }
+
+            Ok(instruction)
+        })
+        .collect()
+}
+
+/// The ferry's docking program decoder, parameterized by how it writes memory.
+pub struct Computer {
+    mask: (u64, u64, u64),
+    memory: HashMap<u64, u64>,
+    set_method: fn(&mut Self, u64, u64) -> Result<()>,
+    checkpoint: Checkpoint,
+}
+
+impl Computer {
+    pub fn new(set_method: fn(&mut Self, u64, u64) -> Result<()>) -> Self {
+        Self {
+            mask: (0, 0, 0),
+            memory: HashMap::new(),
+            set_method,
+            checkpoint: Checkpoint::new(14),
+        }
+    }
+
+    /// Gives up if the solver runs out of budget, as a mask with many floating bits
+    /// makes it write to billions of addresses.
+    pub fn run(&mut self, instructions: &[Instruction]) -> Result<()> {
+        for instruction in instructions {
+            match instruction {
+                Instruction::Mask(mask) => self.set_mask(mask),
+                Instruction::Assignment(address, value) => {
+                    (self.set_method)(self, *address, *value)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Version 1: the mask overwrites bits of the value.
+    pub fn set_fixed(&mut self, address: u64, value: u64) -> Result<()> {
+        self.memory
+            .insert(address, (value | self.mask.0) & !self.mask.1);
+
+        Ok(())
+    }
+
+    const MAX_INDEX: u64 = 1 << 36;
+
+    fn set_floating_acc(&mut self, address: u64, value: u64, index: u64) -> Result<()> {
+        if index == Self::MAX_INDEX {
+            self.memory.insert(address, value);
+            self.checkpoint.tick()
+        } else {
+            self.set_floating_acc(address | (self.mask.0 & index), value, index << 1)?;
+            if self.mask.2 & index == index {
+                self.set_floating_acc(address ^ index, value, index << 1)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Version 2: the mask decodes the address into every floating combination.
+    pub fn set_floating(&mut self, address: u64, value: u64) -> Result<()> {
+        self.set_floating_acc(address, value, 1)
+    }
+
+    /// [`Computer::set_floating`] without the recursion: writes to every combination
+    /// of the floating bits in turn.
+    pub fn set_floating_reference(&mut self, address: u64, value: u64) -> Result<()> {
+        let (ones, _, floating) = self.mask;
+        let bits: Vec<u64> = (0..36).map(|i| 1 << i).filter(|bit| floating & bit != 0).collect();
+
+        for combination in 0..1u64 << bits.len() {
+            let mut decoded = (address | ones) & !floating;
+
+            for (i, bit) in bits.iter().enumerate() {
+                if combination >> i & 1 == 1 {
+                    decoded |= bit;
+                }
+            }
+
+            self.memory.insert(decoded, value);
+            self.checkpoint.tick()?;
+        }
+
+        Ok(())
+    }
+
+    fn set_mask(&mut self, mask: &str) {
+        let (mut ones, mut zeros, mut exes) = (0, 0, 0);
+
+        for c in mask.chars() {
+            ones <<= 1;
+            zeros <<= 1;
+            exes <<= 1;
+
+            match c {
+                '0' => zeros |= 1,
+                '1' => ones |= 1,
+                'X' => exes |= 1,
+                _ => unreachable!(),
+            };
+        }
+
+        self.mask = (ones, zeros, exes);
+    }
+
+    pub fn total(&self) -> u64 {
+        self.memory.values().sum()
+    }
+}
+
+#[aoc(day14, part1)]
+pub fn part1(program: &[Instruction]) -> Result<u64> {
+    let mut computer = Computer::new(Computer::set_fixed);
+
+    computer.run(program)?;
+
+    Ok(computer.total())
+}
+
+#[aoc(day14, part2)]
+pub fn part2(program: &[Instruction]) -> Result<u64> {
+    let mut computer = Computer::new(Computer::set_floating);
+
+    computer.run(program)?;
+
+    Ok(computer.total())
+}
+
+pub fn part2_reference(program: &[Instruction]) -> Result<u64> {
+    let mut computer = Computer::new(Computer::set_floating_reference);
+
+    computer.run(program)?;
+
+    Ok(computer.total())
+}
+
+/// About `size` instructions, with a new mask every few writes. Masks have at most
+/// nine floating bits, so version 2 writes at most 512 addresses each.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let mut lines = Vec::new();
+
+    while lines.len() < size.max(2) {
+        let floating = rng.range(0..=9) as usize;
+        let mut mask: Vec<char> = (0..36)
+            .map(|i| if i < floating { 'X' } else { *rng.choose(&['0', '1']) })
+            .collect();
+        rng.shuffle(&mut mask);
+
+        lines.push(Instruction::Mask(mask.into_iter().collect()));
+
+        for _ in 0..rng.range(1..=6) {
+            lines.push(Instruction::Assignment(rng.below(1 << 16), rng.below(1 << 30)));
+        }
+    }
+
+    Synthetic::new(synth::lines(lines))
+}
@@ -0,0 +1,75 @@
+//! Day 15: Rambunctious Recitation.
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::{
+    error::{Error, Result},
+    input::Input,
+    limits::Checkpoint,
+    synth::{Rng, Synthetic},
+};
+
+#[aoc_generator(day15)]
+pub fn parse(input: &str) -> Result<Vec<u32>> {
+    let input = Input::new(15, input);
+    let line = input
+        .lines()
+        .next()
+        .ok_or_else(|| Error::unexpected_end(15, 1, "the starting numbers"))?;
+
+    line.text.split(',').map(|n| line.parse_token(n)).collect()
+}
+
+/// The `n`th number spoken in the memory game starting from `seed`.
+pub fn sequence_nth(seed: &[u32], n: u32) -> Result<u32> {
+    let rounds = n.saturating_sub(seed.len() as u32 + 1);
+    let mut checkpoint = Checkpoint::new(15).with_total(u64::from(rounds));
+
+    // Numbers spoken after the seed are turn gaps, so always below `n`.
+    let len = seed.iter().map(|&i| i + 1).chain(Some(n)).max().unwrap() as usize;
+    checkpoint.reserve(len * std::mem::size_of::<u32>(), "the table of turns")?;
+
+    let mut seen = vec![0; len];
+    let mut turn = 0;
+
+    for &i in seed {
+        turn += 1;
+        seen[i as usize] = turn;
+    }
+
+    let mut next = 0;
+
+    while turn + 1 < n {
+        turn += 1;
+        checkpoint.tick()?;
+
+        let last_seen = std::mem::replace(&mut seen[next as usize], turn);
+
+        if last_seen == 0 {
+            next = 0;
+        } else {
+            next = turn - last_seen;
+        }
+    }
+
+    Ok(next)
+}
+
+#[aoc(day15, part1)]
+pub fn part1(seed: &[u32]) -> Result<u32> {
+    sequence_nth(seed, 2020)
+}
+
+#[aoc(day15, part2)]
+pub fn part2(seed: &[u32]) -> Result<u32> {
+    sequence_nth(seed, 30000000)
+}
+
+/// Up to `size` distinct starting numbers, at most seven like the puzzle's.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let mut numbers: Vec<u32> = (0..20).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(size.clamp(1, 7));
+
+    Synthetic::new(numbers.iter().map(u32::to_string).collect::<Vec<_>>().join(","))
+}
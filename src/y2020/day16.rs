@@ -0,0 +1,248 @@
+//! Day 16: Ticket Translation.
+
+use std::collections::HashSet;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+use parse_display::{Display, FromStr};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Line, Result},
+    explain,
+    input::Input,
+    synth::{self, Rng, Synthetic},
+};
+
+/// A ticket field valid for values in `a..=b` or `c..=d`.
+#[derive(Display, FromStr, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[display("{name}: {a}-{b} or {c}-{d}")]
+pub struct Field {
+    pub name: String,
+    pub a: usize,
+    pub b: usize,
+    pub c: usize,
+    pub d: usize,
+}
+
+impl Field {
+    pub fn contains(&self, value: usize) -> bool {
+        (self.a..=self.b).contains(&value) || (self.c..=self.d).contains(&value)
+    }
+}
+
+/// The ticket fields, my ticket and the nearby tickets.
+pub type Notes = (Vec<Field>, Vec<usize>, Vec<Vec<usize>>);
+
+#[aoc_generator(day16)]
+pub fn parse(input: &str) -> Result<Notes> {
+    let input = Input::new(16, input);
+    let last_line = input.section().last_line();
+    let mut sections = input.sections();
+
+    let mut next_section = |header: &str| {
+        let section = sections
+            .next()
+            .ok_or_else(|| Error::unexpected_end(16, last_line, header))?;
+
+        let mut lines = section.lines();
+
+        if !header.is_empty() {
+            match lines.next() {
+                Some(line) if line.text == header => (),
+                Some(line) => return Err(line.error(line.text, format!("expected {:?}", header))),
+                None => return Err(Error::unexpected_end(16, section.first_line(), header)),
+            }
+        }
+
+        Ok(lines)
+    };
+
+    let fields: Vec<Field> = next_section("")?
+        .map(|line| line.parse_format("{name}: {a}-{b} or {c}-{d}"))
+        .collect::<Result<_>>()?;
+
+    let parse_ticket = |line: Line| {
+        let ticket: Vec<usize> = line
+            .text
+            .split(',')
+            .map(|n| line.parse_token(n))
+            .collect::<Result<_>>()?;
+
+        if ticket.len() != fields.len() {
+            return Err(line.error(line.text, format!("expected {} values", fields.len())));
+        }
+
+        Ok(ticket)
+    };
+
+    let my_ticket = next_section("your ticket:")?
+        .next()
+        .ok_or_else(|| Error::unexpected_end(16, last_line, "my ticket"))
+        .and_then(parse_ticket)?;
+
+    let nearby_tickets = next_section("nearby tickets:")?
+        .map(parse_ticket)
+        .collect::<Result<_>>()?;
+
+    Ok((fields, my_ticket, nearby_tickets))
+}
+
+#[aoc(day16, part1)]
+pub fn part1((fields, _, nearby_tickets): &Notes) -> usize {
+    nearby_tickets
+        .iter()
+        .flatten()
+        .filter(|&&value| !fields.iter().any(|field| field.contains(value)))
+        .sum()
+}
+
+#[aoc(day16, part2)]
+pub fn part2((fields, my_ticket, nearby_tickets): &Notes) -> Result<usize> {
+    let valid_tickets = nearby_tickets.iter().filter(|ticket| {
+        ticket
+            .iter()
+            .all(|&value| fields.iter().any(|field| field.contains(value)))
+    });
+
+    let mut possible_fields: Vec<(usize, HashSet<usize>)> = fields
+        .iter()
+        .map(|_| (0..fields.len()).collect())
+        .enumerate()
+        .collect();
+
+    for ticket in valid_tickets {
+        for (&value, (_, possible)) in ticket.iter().zip(&mut possible_fields) {
+            for (i, field) in fields.iter().enumerate() {
+                if !field.contains(value) {
+                    possible.remove(&i);
+                }
+            }
+        }
+    }
+
+    possible_fields.sort_by_key(|(_, possible_fields)| possible_fields.len());
+
+    let mut used = HashSet::new();
+    let mut columns = Vec::new();
+    let mut product = 1;
+
+    for (i, possible) in possible_fields {
+        let field_index = (&possible - &used).into_iter().exactly_one().map_err(|candidates| {
+            match candidates.map(|index| &fields[index].name).sorted().join(", ") {
+                names if names.is_empty() => Error::gave_up(16, format!("column {} fits no field", i)),
+                names => Error::gave_up(16, format!("column {} could be any of {}", i, names)),
+            }
+        })?;
+
+        if fields[field_index].name.starts_with("departure") {
+            product *= my_ticket[i];
+        }
+
+        used.insert(field_index);
+        columns.push((i, field_index));
+    }
+
+    for (i, field_index) in columns.into_iter().sorted() {
+        explain::note(16, &format!("column {}", i), || &fields[field_index].name);
+    }
+
+    Ok(product)
+}
+
+/// The field names on the puzzle's tickets.
+const NAMES: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+/// Notes with up to 20 fields and `size` nearby tickets, about a third of them
+/// holding one invalid value.
+///
+/// The fields' ranges are nested, each narrower than the last, so that the
+/// column holding the `k`th widest field fits exactly `k + 1` fields. A witness
+/// ticket rules out the narrower ones, leaving a single way to assign fields.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let count = size.clamp(3, NAMES.len());
+    let ranges = |rank: usize| (1 + 5 * rank, 479, 521, 999 - 5 * rank);
+    let valid_value = |rng: &mut Rng, rank: usize| {
+        let (a, b, c, d) = ranges(rank);
+        let value = rng.range(a as i64..=(b + d - c + 1) as i64) as usize;
+
+        if value <= b {
+            value
+        } else {
+            value - b - 1 + c
+        }
+    };
+
+    let mut names = NAMES[..count].to_vec();
+    rng.shuffle(&mut names);
+
+    let mut listed: Vec<usize> = (0..count).collect();
+    rng.shuffle(&mut listed);
+
+    let mut columns: Vec<usize> = (0..count).collect();
+    rng.shuffle(&mut columns);
+
+    let my_ticket: Vec<usize> = columns.iter().map(|&rank| valid_value(rng, rank)).collect();
+    let witness: Vec<usize> = columns.iter().map(|&rank| 1 + 5 * rank + rng.index(5)).collect();
+    let mut nearby_tickets = vec![witness];
+    let mut error_rate = 0;
+
+    while nearby_tickets.len() < size.max(1) {
+        let mut ticket: Vec<usize> = columns.iter().map(|&rank| valid_value(rng, rank)).collect();
+
+        if rng.chance(0.3) {
+            let value = if rng.chance(0.5) { rng.range(480..=520) } else { rng.range(1000..=1100) } as usize;
+
+            ticket[rng.index(count)] = value;
+            error_rate += value;
+        }
+
+        nearby_tickets.push(ticket);
+    }
+
+    rng.shuffle(&mut nearby_tickets);
+
+    let departure: usize = columns
+        .iter()
+        .zip(&my_ticket)
+        .filter(|&(&rank, _)| names[rank].starts_with("departure"))
+        .map(|(_, &value)| value)
+        .product();
+
+    let fields = listed.iter().map(|&rank| {
+        let (a, b, c, d) = ranges(rank);
+        Field { name: names[rank].to_string(), a, b, c, d }
+    });
+
+    let ticket = |ticket: &Vec<usize>| ticket.iter().join(",");
+
+    let input = format!(
+        "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+        synth::lines(fields),
+        ticket(&my_ticket),
+        synth::lines(nearby_tickets.iter().map(ticket)),
+    );
+
+    Synthetic::with_answers(input, vec![Some(error_rate.to_string()), Some(departure.to_string())])
+}
@@ -0,0 +1,105 @@
+//! Day 17: Conway Cubes.
+
+use std::collections::HashMap;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::{
+    automaton::{Automaton, Lattice, Mode},
+    error::Result,
+    frames::{self, Frame, Recorder},
+    input::Input,
+    limits::Checkpoint,
+    synth::{self, Rng, Synthetic},
+};
+
+#[aoc_generator(day17)]
+pub fn parse(input: &str) -> Result<Vec<(i32, i32)>> {
+    let mut active = Vec::new();
+
+    for (i, line) in Input::new(17, input).lines().enumerate() {
+        let cubes = line.map_chars("'.' or '#'", |c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        })?;
+
+        active.extend(
+            cubes
+                .into_iter()
+                .enumerate()
+                .filter(|&(_, cube)| cube)
+                .map(|(j, _)| (i as i32, j as i32)),
+        );
+    }
+
+    Ok(active)
+}
+
+pub const RULE: &str = "B3/S23";
+pub const CYCLES: usize = 6;
+
+/// Runs the boot cycles with the initial slice embedded in `dimensions` dimensions,
+/// and counts the active cubes. Gives up if the solver runs out of budget first.
+pub fn boot(initial: &[(i32, i32)], dimensions: usize, recorder: &mut Recorder) -> Result<usize> {
+    let lattice = Lattice::new(dimensions);
+    let active: Vec<Vec<i32>> = initial.iter().map(|&(x, y)| lattice.embed(&[x, y])).collect();
+
+    let mut automaton = Automaton::new(lattice, RULE.parse().expect("invalid rule"), active);
+
+    let mut checkpoint = Checkpoint::new(17).with_total(CYCLES as u64).with_stride(1);
+
+    automaton.run_checked(Mode::Generations(CYCLES), &mut checkpoint, |automaton| {
+        recorder.record(|| render(automaton))
+    })?;
+
+    Ok(automaton.population())
+}
+
+/// Projects the active cubes onto the initial slice, brighter where more are stacked.
+pub fn render(automaton: &Automaton<Lattice>) -> Frame {
+    let mut stacked: HashMap<(i64, i64), usize> = HashMap::new();
+
+    for point in automaton.live() {
+        *stacked.entry((point[1] as i64, point[0] as i64)).or_default() += 1;
+    }
+
+    let max = stacked.values().copied().max().unwrap_or(1);
+
+    frames::render_points(
+        stacked
+            .into_iter()
+            .map(|(pos, count)| (pos, (55 + 200 * count / max) as u8)),
+    )
+}
+
+#[aoc(day17, part1)]
+pub fn part1(active: &[(i32, i32)]) -> Result<usize> {
+    part1_traced(active, &mut Recorder::disabled())
+}
+
+pub fn part1_traced(active: &[(i32, i32)], recorder: &mut Recorder) -> Result<usize> {
+    boot(active, 3, recorder)
+}
+
+#[aoc(day17, part2)]
+pub fn part2(active: &[(i32, i32)]) -> Result<usize> {
+    part2_traced(active, &mut Recorder::disabled())
+}
+
+pub fn part2_traced(active: &[(i32, i32)], recorder: &mut Recorder) -> Result<usize> {
+    boot(active, 4, recorder)
+}
+
+/// A square initial slice up to 8 cubes wide like the puzzle's, larger ones being
+/// slow to boot in four dimensions.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let side = size.clamp(3, 8);
+    let rows = (0..side).map(|_| {
+        (0..side)
+            .map(|_| if rng.chance(0.45) { '#' } else { '.' })
+            .collect::<String>()
+    });
+
+    Synthetic::new(synth::lines(rows))
+}
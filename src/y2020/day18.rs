@@ -0,0 +1,176 @@
+//! Day 18: Operation Order.
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use pest::iterators::Pair;
+use pest::Parser;
+use pest_derive::Parser;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    input::Input,
+    synth::{self, Rng, Synthetic},
+};
+
+#[derive(Parser)]
+#[grammar = "y2020/grammars/day18.pest"]
+struct MathParser;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operator {
+    Add,
+    Multiply,
+}
+
+impl Operator {
+    pub fn apply(self, lhs: u64, rhs: u64) -> u64 {
+        match self {
+            Operator::Add => lhs + rhs,
+            Operator::Multiply => lhs * rhs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Term {
+    Int(u64),
+    Group(Box<Expression>),
+}
+
+/// A flat sequence of terms and operators, evaluated according to a precedence.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Expression {
+    pub first: Term,
+    pub rest: Vec<(Operator, Term)>,
+}
+
+impl Term {
+    fn from_pair(pair: Pair<Rule>) -> Result<Self> {
+        match pair.as_rule() {
+            Rule::int => pair.as_str().parse().map(Term::Int).map_err(|err| {
+                let (line, column) = pair.as_span().start_pos().line_col();
+                Error::parse(18, line, column, pair.as_str(), err)
+            }),
+            Rule::expression => Ok(Term::Group(Box::new(Expression::from_pair(pair)?))),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn eval(&self, precedence: fn(Operator) -> u8) -> u64 {
+        match self {
+            Term::Int(i) => *i,
+            Term::Group(expression) => expression.eval(precedence),
+        }
+    }
+}
+
+impl Expression {
+    fn from_pair(pair: Pair<Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+
+        let first = Term::from_pair(inner.next().unwrap())?;
+        let mut rest = Vec::new();
+
+        while let Some(op) = inner.next() {
+            let op = match op.as_rule() {
+                Rule::add => Operator::Add,
+                Rule::multiply => Operator::Multiply,
+                _ => unreachable!(),
+            };
+
+            rest.push((op, Term::from_pair(inner.next().unwrap())?));
+        }
+
+        Ok(Self { first, rest })
+    }
+
+    /// Evaluates left to right, applying higher `precedence` operators first.
+    pub fn eval(&self, precedence: fn(Operator) -> u8) -> u64 {
+        let mut values = vec![self.first.eval(precedence)];
+        let mut operators: Vec<Operator> = Vec::new();
+
+        let reduce = |values: &mut Vec<u64>, op: Operator| {
+            let rhs = values.pop().unwrap();
+            let lhs = values.pop().unwrap();
+            values.push(op.apply(lhs, rhs));
+        };
+
+        for &(op, ref term) in &self.rest {
+            while let Some(&top) = operators.last() {
+                if precedence(top) < precedence(op) {
+                    break;
+                }
+
+                operators.pop();
+                reduce(&mut values, top);
+            }
+
+            operators.push(op);
+            values.push(term.eval(precedence));
+        }
+
+        while let Some(op) = operators.pop() {
+            reduce(&mut values, op);
+        }
+
+        values[0]
+    }
+}
+
+#[aoc_generator(day18)]
+pub fn parse(input: &str) -> Result<Vec<Expression>> {
+    let input = Input::new(18, input);
+
+    MathParser::parse(Rule::homework, input.text())
+        .map_err(|err| Error::from_pest(18, 1, input.text(), err))?
+        .filter(|pair| !matches!(pair.as_rule(), Rule::EOI))
+        .map(Expression::from_pair)
+        .collect()
+}
+
+#[aoc(day18, part1)]
+pub fn part1(homework: &[Expression]) -> u64 {
+    homework.iter().map(|expr| expr.eval(|_| 0)).sum()
+}
+
+#[aoc(day18, part2)]
+pub fn part2(homework: &[Expression]) -> u64 {
+    homework
+        .iter()
+        .map(|expr| {
+            expr.eval(|op| match op {
+                Operator::Add => 1,
+                Operator::Multiply => 0,
+            })
+        })
+        .sum()
+}
+
+/// An expression of two to four single digit terms, and groups of such terms
+/// `depth` levels deep. Two levels keep every result within a `u64`.
+fn synthesize_expression(rng: &mut Rng, depth: usize) -> String {
+    let mut expression = String::new();
+
+    for i in 0..rng.range(2..=4) {
+        if i > 0 {
+            expression.push_str(if rng.chance(0.5) { " + " } else { " * " });
+        }
+
+        if depth > 1 && rng.chance(0.3) {
+            expression.push('(');
+            expression.push_str(&synthesize_expression(rng, depth - 1));
+            expression.push(')');
+        } else {
+            expression.push_str(&rng.range(1..=9).to_string());
+        }
+    }
+
+    expression
+}
+
+/// `size` lines of homework.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    Synthetic::new(synth::lines((0..size.max(1)).map(|_| synthesize_expression(rng, 2))))
+}
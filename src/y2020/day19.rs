@@ -0,0 +1,391 @@
+//! Day 19: Monster Messages.
+
+use std::collections::{HashMap, HashSet};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use pest_derive::Parser;
+use pest::{Parser, iterators::Pair};
+use itertools::Itertools;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    input::{Input, Section},
+    synth::{self, Rng, Synthetic},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageRule {
+    /// A rule number which was never defined.
+    Nul,
+    Char(char),
+    Alt(Vec<Vec<usize>>)
+}
+
+/// The message rules, indexed by rule number, and the received messages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Messages {
+    pub rules: Vec<MessageRule>,
+    pub messages: Vec<String>,
+}
+
+/// Matches messages against rule 0, compiled to a regex.
+#[derive(Parser)]
+#[grammar = "y2020/grammars/day19.pest"]
+pub struct RuleParser {
+    regex: Regex,
+}
+
+impl RuleParser {
+    fn parse_int(pair: Pair<Rule>) -> Result<usize> {
+        pair.as_str().parse().map_err(|err| {
+            let (line, column) = pair.as_span().start_pos().line_col();
+            Error::parse(19, line, column, pair.as_str(), err)
+        })
+    }
+
+    fn parse_seq(pair: Pair<Rule>) -> Result<Vec<usize>> {
+        pair
+            .into_inner()
+            .map(Self::parse_int)
+            .collect()
+    }
+
+    fn parse_alt(pair: Pair<Rule>) -> Result<Vec<Vec<usize>>> {
+        pair
+            .into_inner()
+            .map(Self::parse_seq)
+            .collect()
+    }
+
+    fn parse_char(pair: Pair<Rule>) -> char {
+        pair.as_str().chars().exactly_one().unwrap()
+    }
+
+    fn parse_rules(grammar: Section) -> Result<Vec<MessageRule>> {
+        let mut rules = Vec::new();
+        let mut references = Vec::new();
+        let mut definitions = HashMap::new();
+
+        let pairs = Self::parse(Rule::all, grammar.text())
+            .map_err(|err| Error::from_pest(19, grammar.first_line(), grammar.text(), err))?;
+
+        for rule in pairs {
+            if matches!(rule.as_rule(), Rule::EOI) {
+                continue
+            }
+
+            let (index, value) = rule.into_inner().next_tuple().unwrap();
+
+            let definition = index.clone();
+            let index = Self::parse_int(index)?;
+            definitions.insert(index, definition);
+
+            let value = match value.as_rule() {
+                Rule::char => MessageRule::Char(Self::parse_char(value)),
+                Rule::alt => {
+                    references.extend(value.clone().into_inner().flat_map(Pair::into_inner));
+                    MessageRule::Alt(Self::parse_alt(value)?)
+                },
+                _ => unreachable!()
+            };
+
+            if index >= rules.len() {
+                rules.extend((rules.len()..=index).map(|_| MessageRule::Nul))
+            }
+
+            rules[index] = value;
+        }
+
+        if matches!(rules.first(), None | Some(MessageRule::Nul)) {
+            return Err(Error::unexpected_end(19, 1, "rule 0"));
+        }
+
+        for reference in references {
+            let index = Self::parse_int(reference.clone())?;
+
+            if matches!(rules.get(index), None | Some(MessageRule::Nul)) {
+                let (line, column) = reference.as_span().start_pos().line_col();
+                return Err(Error::parse(19, line, column, reference.as_str(), "undefined rule"));
+            }
+
+            let defined = |index| !matches!(rules.get(index), None | Some(MessageRule::Nul));
+
+            if [8, 11].contains(&index) && !(defined(42) && defined(31)) {
+                let (line, column) = reference.as_span().start_pos().line_col();
+                let reason = "part 2 replaces this rule with rules 42 and 31, which must be defined";
+                return Err(Error::parse(19, line, column, reference.as_str(), reason));
+            }
+        }
+
+        if let Some(index) = Self::find_cycle(&rules, 0, &mut Vec::new(), &mut HashSet::new()) {
+            let definition = &definitions[&index];
+            let (line, column) = definition.as_span().start_pos().line_col();
+            return Err(Error::parse(19, line, column, definition.as_str(), "rule refers back to itself"));
+        }
+
+        Ok(rules)
+    }
+
+    /// A rule reachable from `index` which refers back to itself, through the rules
+    /// on `path`. Rules known to be free of cycles are `done`.
+    fn find_cycle(
+        rules: &[MessageRule],
+        index: usize,
+        path: &mut Vec<usize>,
+        done: &mut HashSet<usize>,
+    ) -> Option<usize> {
+        if path.contains(&index) {
+            return Some(index);
+        } else if done.contains(&index) {
+            return None;
+        }
+
+        path.push(index);
+
+        if let Some(MessageRule::Alt(alternatives)) = rules.get(index) {
+            for &next in alternatives.iter().flatten() {
+                if let Some(cycle) = Self::find_cycle(rules, next, path, done) {
+                    return Some(cycle);
+                }
+            }
+        }
+
+        path.pop();
+        done.insert(index);
+        None
+    }
+
+    fn format(
+        rules: &[MessageRule],
+        index: usize,
+        regex: &mut String,
+        part2: bool,
+        path: &mut Vec<usize>,
+    ) -> Result<()> {
+        if path.contains(&index) {
+            return Err(Error::gave_up(19, format!("rule {} refers back to itself", index)));
+        }
+
+        path.push(index);
+
+        match rules.get(index) {
+            Some(MessageRule::Char(c)) => regex.push_str(&regex::escape(&c.to_string())),
+            Some(MessageRule::Alt(alternatives)) => {
+                regex.push('(');
+                for (i, seq) in alternatives.iter().enumerate() {
+                    if i > 0 {
+                        regex.push('|')
+                    }
+
+                    for &index in seq {
+                        if part2 && index == 8 {
+                            Self::format(rules, 42, regex, part2, path)?;
+                            regex.push('+');
+                        } else if part2 && index == 11 {
+                            regex.push('(');
+                            for i in 1..10 {
+                                if i > 1 {
+                                    regex.push('|');
+                                }
+                                Self::format(rules, 42, regex, part2, path)?;
+                                regex.push('{');
+                                regex.push((b'0' + i) as char);
+                                regex.push('}');
+                                Self::format(rules, 31, regex, part2, path)?;
+                                regex.push('{');
+                                regex.push((b'0' + i) as char);
+                                regex.push('}');
+                            }
+                            regex.push(')');
+                        } else {
+                            Self::format(rules, index, regex, part2, path)?;
+                        }
+                    }
+                }
+                regex.push(')');
+            },
+            _ => return Err(Error::gave_up(19, format!("rule {} is not defined", index))),
+        }
+
+        path.pop();
+        Ok(())
+    }
+
+    /// Compiles rule 0. With `part2`, rules 8 and 11 are replaced by their
+    /// looping versions, made of rules 42 and 31.
+    pub fn new(rules: &[MessageRule], part2: bool) -> Result<Self> {
+        let mut regex = String::new();
+
+        regex.push('^');
+
+        Self::format(rules, 0, &mut regex, part2, &mut Vec::new())?;
+
+        regex.push('$');
+
+        Ok(Self {
+            regex: Regex::new(&regex).map_err(|err| Error::gave_up(19, err))?
+        })
+    }
+
+    pub fn matches(&self, value: &str) -> bool {
+        self.regex.is_match(value)
+    }
+}
+
+#[aoc_generator(day19)]
+pub fn parse(input: &str) -> Result<Messages> {
+    let input = Input::new(19, input);
+    let mut sections = input.sections();
+
+    let grammar = sections.next().unwrap_or_else(|| input.section());
+    let rules = RuleParser::parse_rules(grammar)?;
+
+    let messages = sections
+        .next()
+        .ok_or_else(|| Error::unexpected_end(19, grammar.last_line(), "the received messages"))?
+        .lines()
+        .map(|line| line.text.to_string())
+        .collect();
+
+    Ok(Messages { rules, messages })
+}
+
+#[aoc(day19, part1)]
+pub fn part1(input: &Messages) ->  Result<usize> {
+    let parser = RuleParser::new(&input.rules, false)?;
+
+    Ok(input
+        .messages
+        .iter()
+        .filter(|l| parser.matches(l))
+        .count())
+}
+
+#[aoc(day19, part2)]
+pub fn part2(input: &Messages) ->  Result<usize> {
+    let parser = RuleParser::new(&input.rules, true)?;
+
+    Ok(input
+        .messages
+        .iter()
+        .filter(|l| parser.matches(l))
+        .count())
+}
+
+/// Adds the rules matching exactly the strings of `set`, all of the same length,
+/// and returns its alternatives. `next` is the next free rule number.
+fn synthesize_rules(set: &[String], rules: &mut Vec<(usize, String)>, next: &mut usize) -> Vec<Vec<usize>> {
+    let mut alternatives = Vec::new();
+
+    for (c, char_rule) in [('a', 1), ('b', 2)] {
+        let rest: Vec<String> = set.iter().filter_map(|s| s.strip_prefix(c)).map(str::to_string).collect();
+
+        if rest.is_empty() {
+            continue;
+        }
+
+        if rest[0].is_empty() {
+            alternatives.push(vec![char_rule]);
+            continue;
+        }
+
+        let sub = synthesize_rules(&rest, rules, next);
+        let index = match sub.as_slice() {
+            [seq] if seq.len() == 1 => seq[0],
+            _ => {
+                while [8, 11, 31, 42].contains(next) {
+                    *next += 1;
+                }
+
+                let index = *next;
+                *next += 1;
+                rules.push((index, format_alternatives(&sub)));
+                index
+            }
+        };
+
+        alternatives.push(vec![char_rule, index]);
+    }
+
+    alternatives
+}
+
+fn format_alternatives(alternatives: &[Vec<usize>]) -> String {
+    alternatives.iter().map(|seq| seq.iter().join(" ")).join(" | ")
+}
+
+/// Rules like the puzzle's, in which rules 42 and 31 split the strings of a few
+/// letters between them, and `size` messages made of such chunks.
+///
+/// A message of chunks matching 42 `x` times then 31 `y` times is valid in part 2
+/// if `x > y >= 1`, and in part 1 only for `x == 2 && y == 1`.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let len = rng.range(3..=5) as usize;
+
+    let mut chunks: Vec<String> = (0..1 << len)
+        .map(|n: usize| (0..len).rev().map(|bit| if n >> bit & 1 == 1 { 'b' } else { 'a' }).collect())
+        .collect();
+    rng.shuffle(&mut chunks);
+
+    let (forty_two, thirty_one) = chunks.split_at(rng.range(1..=(chunks.len() - 1) as i64) as usize);
+
+    let mut rules = vec![
+        (0, "8 11".to_string()),
+        (1, "\"a\"".to_string()),
+        (2, "\"b\"".to_string()),
+        (8, "42".to_string()),
+        (11, "42 31".to_string()),
+    ];
+
+    let mut next = 3;
+    let alternatives = synthesize_rules(forty_two, &mut rules, &mut next);
+    rules.push((42, format_alternatives(&alternatives)));
+    let alternatives = synthesize_rules(thirty_one, &mut rules, &mut next);
+    rules.push((31, format_alternatives(&alternatives)));
+
+    rng.shuffle(&mut rules);
+
+    let (mut part1, mut part2) = (0, 0);
+
+    let messages: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let (x, y) = if rng.chance(0.3) { (2, 1) } else { (rng.range(1..=6), rng.range(0..=4)) };
+
+            let mut kinds: Vec<bool> = (0..x).map(|_| true).chain((0..y).map(|_| false)).collect();
+
+            if rng.chance(0.2) {
+                rng.shuffle(&mut kinds);
+            }
+
+            let mut message: String = kinds
+                .iter()
+                .map(|&is_42| if is_42 { rng.choose(forty_two) } else { rng.choose(thirty_one) }.as_str())
+                .collect();
+
+            if rng.chance(0.1) {
+                message.push(*rng.choose(&['a', 'b']));
+            } else {
+                let split = kinds.iter().position(|&is_42| !is_42).unwrap_or(kinds.len());
+                let (x, y) = (split, kinds.len() - split);
+
+                if kinds[split..].iter().all(|&is_42| !is_42) {
+                    part1 += (x == 2 && y == 1) as usize;
+                    part2 += (x > y && y >= 1) as usize;
+                }
+            }
+
+            message
+        })
+        .collect();
+
+    let input = format!(
+        "{}\n\n{}",
+        synth::lines(rules.iter().map(|(index, rule)| format!("{}: {}", index, rule))),
+        synth::lines(messages)
+    );
+
+    Synthetic::with_answers(input, vec![Some(part1.to_string()), Some(part2.to_string())])
+}
@@ -0,0 +1,711 @@
+//! Day 2: Password Philosophy.
+
+use std::{borrow::Cow, collections::BTreeMap, convert::TryFrom, fmt, num::NonZeroUsize};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+use parse_display::{Display, FromStr};
+use pest::{iterators::Pair, Parser};
+use pest_derive::Parser;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    error::{Error, Result},
+    input::Input,
+    synth::{self, Rng, Synthetic},
+};
+
+/// A password database entry and the policy it was created under.
+#[derive(Display, FromStr, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[display("{start}-{end} {char}: {password}")]
+pub struct Line {
+    pub start: usize,
+    pub end: usize,
+    pub char: char,
+    pub password: String,
+}
+
+#[aoc_generator(day2)]
+pub fn parse(input: &str) -> Result<Vec<Line>> {
+    Input::new(2, input)
+        .lines()
+        .map(|line| line.parse_format("{start}-{end} {char}: {password}"))
+        .collect()
+}
+
+#[aoc(day2, part1)]
+pub fn part1(lines: &[Line]) -> Result<usize> {
+    count_allowed(lines, &Count)
+}
+
+#[aoc(day2, part2)]
+pub fn part2(lines: &[Line]) -> Result<usize> {
+    count_allowed(lines, &Positions::default())
+}
+
+/// How many entries' passwords `policy` allows, once every entry is checked to
+/// make sense to it.
+pub fn count_allowed(lines: &[Line], policy: &dyn PasswordPolicy) -> Result<usize> {
+    for (line, number) in lines.iter().zip(1..) {
+        validate_entry(number, line, policy)?;
+    }
+
+    Ok(lines.iter().filter(|line| policy.allows(line)).count())
+}
+
+/// Checks that every entry makes sense to `policy`, e.g. that positions are 1-based.
+pub fn validate(entries: &[(usize, Line)], policy: &dyn PasswordPolicy) -> Result<()> {
+    entries.iter().try_for_each(|(number, line)| validate_entry(*number, line, policy))
+}
+
+fn validate_entry(number: usize, line: &Line, policy: &dyn PasswordPolicy) -> Result<()> {
+    policy.validate(line).map_err(|reason| {
+        let reason = format!("{} does not apply ({})", policy, reason);
+        Error::parse(2, number, 1, &line.to_string(), reason)
+    })
+}
+
+/// A rule passwords must follow. Displays as it is written in [`parse_policy`].
+pub trait PasswordPolicy: fmt::Display + Send + Sync {
+    /// Whether `line`'s password follows the policy. Entries which do not make
+    /// sense to it, see [`PasswordPolicy::validate`], do not.
+    fn allows(&self, line: &Line) -> bool;
+
+    /// Checks that `line` makes sense to the policy, or why not.
+    fn validate(&self, _line: &Line) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// The policies `line`'s password breaks: this one, or the parts of it which
+    /// are to blame.
+    fn violations(&self, line: &Line) -> Vec<String> {
+        if self.allows(line) {
+            Vec::new()
+        } else {
+            vec![self.to_string()]
+        }
+    }
+}
+
+/// The entry's character occurs between `start` and `end` times, the policy of part 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Count;
+
+impl PasswordPolicy for Count {
+    fn allows(&self, line: &Line) -> bool {
+        let count = line.password.chars().filter(|&c| c == line.char).count();
+        (line.start..=line.end).contains(&count)
+    }
+}
+
+impl fmt::Display for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("count")
+    }
+}
+
+/// What positions and lengths in a password count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display, FromStr)]
+#[display(style = "lowercase")]
+pub enum Units {
+    /// Unicode scalar values, as the puzzle does.
+    #[default]
+    Chars,
+    /// What readers see as one character, e.g. `é` written as `e` and a combining
+    /// accent.
+    Graphemes,
+}
+
+impl Units {
+    /// Splits `password` into units.
+    pub fn split(self, password: &str) -> Box<dyn Iterator<Item = &str> + '_> {
+        match self {
+            Units::Chars => {
+                Box::new(password.char_indices().map(move |(i, c)| &password[i..i + c.len_utf8()]))
+            }
+            Units::Graphemes => Box::new(password.graphemes(true)),
+        }
+    }
+}
+
+/// A 1-based position in a password.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position(NonZeroUsize);
+
+impl Position {
+    /// The position `position`, unless it is 0.
+    pub fn new(position: usize) -> Option<Self> {
+        NonZeroUsize::new(position).map(Position)
+    }
+
+    pub fn get(self) -> usize {
+        self.0.get()
+    }
+
+    /// The unit of `password` at this position, unless it is too short.
+    pub fn of(self, password: &str, units: Units) -> Option<&str> {
+        units.split(password).nth(self.get() - 1)
+    }
+}
+
+impl TryFrom<usize> for Position {
+    type Error = String;
+
+    fn try_from(position: usize) -> Result<Self, Self::Error> {
+        Position::new(position).ok_or_else(|| "positions start at 1".to_string())
+    }
+}
+
+/// The entry's character is at exactly one of the positions `start` and `end`, the
+/// policy of part 2.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Positions {
+    pub units: Units,
+}
+
+impl Positions {
+    fn positions(line: &Line) -> Result<(Position, Position), String> {
+        Ok((Position::try_from(line.start)?, Position::try_from(line.end)?))
+    }
+}
+
+impl PasswordPolicy for Positions {
+    fn allows(&self, line: &Line) -> bool {
+        let (start, end) = match Self::positions(line) {
+            Ok(positions) => positions,
+            Err(_) => return false,
+        };
+
+        let mut buffer = [0; 4];
+        let char = Some(&*line.char.encode_utf8(&mut buffer));
+
+        (start.of(&line.password, self.units) == char) ^ (end.of(&line.password, self.units) == char)
+    }
+
+    fn validate(&self, line: &Line) -> Result<(), String> {
+        Self::positions(line).map(|_| ())
+    }
+}
+
+impl fmt::Display for Positions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.units {
+            Units::Chars => f.write_str("positions"),
+            units => write!(f, "positions {}", units),
+        }
+    }
+}
+
+/// The password is at least this long.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinLength {
+    pub len: usize,
+    pub units: Units,
+}
+
+impl PasswordPolicy for MinLength {
+    fn allows(&self, line: &Line) -> bool {
+        self.units.split(&line.password).count() >= self.len
+    }
+}
+
+impl fmt::Display for MinLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.units {
+            Units::Chars => write!(f, "min-length {}", self.len),
+            units => write!(f, "min-length {} {}", self.len, units),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, FromStr)]
+#[display(style = "lowercase")]
+pub enum CharClass {
+    Lower,
+    Upper,
+    Digit,
+    /// Anything else but whitespace.
+    Symbol,
+}
+
+impl CharClass {
+    pub fn contains(self, c: char) -> bool {
+        match self {
+            CharClass::Lower => c.is_lowercase(),
+            CharClass::Upper => c.is_uppercase(),
+            CharClass::Digit => c.is_numeric(),
+            CharClass::Symbol => !c.is_alphanumeric() && !c.is_whitespace(),
+        }
+    }
+}
+
+/// The password has a character of each class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Classes(pub Vec<CharClass>);
+
+impl PasswordPolicy for Classes {
+    fn allows(&self, line: &Line) -> bool {
+        self.0.iter().all(|&class| line.password.chars().any(|c| class.contains(c)))
+    }
+
+    fn violations(&self, line: &Line) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|&&class| !line.password.chars().any(|c| class.contains(c)))
+            .map(|class| format!("classes {}", class))
+            .collect()
+    }
+}
+
+impl fmt::Display for Classes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "classes {}", self.0.iter().join(","))
+    }
+}
+
+/// The password does not contain this.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forbid(pub String);
+
+impl PasswordPolicy for Forbid {
+    fn allows(&self, line: &Line) -> bool {
+        !line.password.contains(&self.0)
+    }
+}
+
+impl fmt::Display for Forbid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "forbid {}", quote(&self.0))
+    }
+}
+
+/// The password matches this regex somewhere, or as a whole if it is anchored.
+#[derive(Debug, Clone)]
+pub struct Matches(pub Regex);
+
+impl PasswordPolicy for Matches {
+    fn allows(&self, line: &Line) -> bool {
+        self.0.is_match(&line.password)
+    }
+}
+
+impl fmt::Display for Matches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "regex {}", quote(self.0.as_str()))
+    }
+}
+
+/// Every one of the policies holds.
+pub struct All(pub Vec<Box<dyn PasswordPolicy>>);
+
+impl PasswordPolicy for All {
+    fn allows(&self, line: &Line) -> bool {
+        self.0.iter().all(|policy| policy.allows(line))
+    }
+
+    fn validate(&self, line: &Line) -> Result<(), String> {
+        self.0.iter().try_for_each(|policy| policy.validate(line))
+    }
+
+    fn violations(&self, line: &Line) -> Vec<String> {
+        self.0.iter().flat_map(|policy| policy.violations(line)).collect()
+    }
+}
+
+impl fmt::Display for All {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.iter().join(" and "))
+    }
+}
+
+/// At least one of the policies holds.
+pub struct Any(pub Vec<Box<dyn PasswordPolicy>>);
+
+impl PasswordPolicy for Any {
+    fn allows(&self, line: &Line) -> bool {
+        self.0.iter().any(|policy| policy.allows(line))
+    }
+
+    fn validate(&self, line: &Line) -> Result<(), String> {
+        self.0.iter().try_for_each(|policy| policy.validate(line))
+    }
+}
+
+impl fmt::Display for Any {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({})", self.0.iter().join(" or "))
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[derive(Parser)]
+#[grammar = "y2020/grammars/day2.pest"]
+struct PolicyParser;
+
+/// Parses a policy such as `count and (min-length 12 or classes lower,upper,digit)`.
+///
+/// The rules are `count`, `positions`, `min-length N`, `classes C,...` of `lower`,
+/// `upper`, `digit` and `symbol`, `forbid "TEXT"` and `regex "PATTERN"`, where `\"`
+/// and `\\` escape quotes and backslashes. `and` binds tighter than `or`. Positions
+/// and lengths count `chars` unless followed by `graphemes`.
+pub fn parse_policy(spec: &str) -> Result<Box<dyn PasswordPolicy>> {
+    let pair = PolicyParser::parse(Rule::policy, spec)
+        .map_err(|err| Error::from_pest(2, 1, spec, err))?
+        .next()
+        .unwrap();
+
+    policy_from_pair(pair)
+}
+
+fn policy_from_pair(pair: Pair<Rule>) -> Result<Box<dyn PasswordPolicy>> {
+    let (line, column) = pair.as_span().start_pos().line_col();
+    let text = pair.as_str();
+
+    Ok(match pair.as_rule() {
+        Rule::all | Rule::any => {
+            let any = pair.as_rule() == Rule::any;
+            let mut policies: Vec<_> = pair.into_inner().map(policy_from_pair).collect::<Result<_>>()?;
+
+            match policies.len() {
+                1 => policies.pop().unwrap(),
+                _ if any => Box::new(Any(policies)),
+                _ => Box::new(All(policies)),
+            }
+        }
+        Rule::count => Box::new(Count),
+        Rule::positions => Box::new(Positions { units: units(pair.into_inner().next()) }),
+        Rule::min_length => {
+            let mut inner = pair.into_inner();
+            let int = inner.next().unwrap();
+            let len = int.as_str().parse().map_err(|err| Error::parse(2, line, column, int.as_str(), err))?;
+            Box::new(MinLength { len, units: units(inner.next()) })
+        }
+        Rule::classes => {
+            let classes = pair.into_inner().map(|class| class.as_str().parse().unwrap());
+            Box::new(Classes(classes.collect()))
+        }
+        Rule::forbid => Box::new(Forbid(unquote(pair.into_inner().next().unwrap()))),
+        Rule::matches => {
+            let pattern = unquote(pair.into_inner().next().unwrap());
+            Box::new(Matches(Regex::new(&pattern).map_err(|err| Error::parse(2, line, column, text, err))?))
+        }
+        _ => unreachable!(),
+    })
+}
+
+fn units(pair: Option<Pair<Rule>>) -> Units {
+    pair.map_or_else(Units::default, |units| units.as_str().parse().unwrap())
+}
+
+/// The contents of a `string`, with escapes undone.
+fn unquote(pair: Pair<Rule>) -> String {
+    let mut unquoted = String::new();
+    let mut escaped = false;
+
+    for c in pair.into_inner().next().unwrap().as_str().chars() {
+        if c == '\\' && !escaped {
+            escaped = true;
+        } else {
+            unquoted.push(c);
+            escaped = false;
+        }
+    }
+
+    unquoted
+}
+
+/// An entry whose password breaks a policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    /// The entry's line in the database, 1-based.
+    pub line: usize,
+    pub entry: String,
+    /// The policies it breaks, e.g. `min-length 12` of `count and min-length 12`.
+    pub violated: Vec<String>,
+}
+
+/// Every entry whose password `policy` does not allow, of entries numbered by
+/// their line in the database.
+pub fn violations(entries: &[(usize, Line)], policy: &dyn PasswordPolicy) -> Vec<Violation> {
+    entries
+        .iter()
+        .filter(|(_, line)| !policy.allows(line))
+        .map(|(number, line)| Violation {
+            line: *number,
+            entry: line.to_string(),
+            violated: policy.violations(line),
+        })
+        .collect()
+}
+
+/// How a database fares against a policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Audit {
+    pub policy: String,
+    pub total: usize,
+    pub allowed: usize,
+    /// How many entries the policy does not allow, by the character they name.
+    pub failures_by_char: BTreeMap<char, usize>,
+    /// The lines of the entries the policy does not allow.
+    pub failed_lines: Vec<usize>,
+}
+
+pub fn audit(entries: &[(usize, Line)], policy: &dyn PasswordPolicy) -> Audit {
+    let mut failures_by_char = BTreeMap::new();
+    let mut failed_lines = Vec::new();
+
+    for (number, line) in entries {
+        if !policy.allows(line) {
+            *failures_by_char.entry(line.char).or_insert(0) += 1;
+            failed_lines.push(*number);
+        }
+    }
+
+    Audit {
+        policy: policy.to_string(),
+        total: entries.len(),
+        allowed: entries.len() - failed_lines.len(),
+        failures_by_char,
+        failed_lines,
+    }
+}
+
+/// A format password databases come in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, FromStr)]
+#[display(style = "kebab-case")]
+pub enum Dialect {
+    /// `1-3 a: abcde`, the puzzle's own.
+    Aoc,
+    /// `start,end,char,password`, with fields quoted where they need to be. A header
+    /// naming the fields may put them in another order.
+    Csv,
+    /// One object per line, like `{"start":1,"end":3,"char":"a","password":"abcde"}`.
+    JsonLines,
+}
+
+/// Parses a database in `dialect`, numbering each entry by its line. Blank lines
+/// are skipped in CSV and JSON lines.
+pub fn parse_dialect(input: &str, dialect: Dialect) -> Result<Vec<(usize, Line)>> {
+    let input = Input::new(2, input);
+    let lines = input.lines();
+
+    match dialect {
+        Dialect::Aoc => lines
+            .map(|line| Ok((line.number, line.parse_format("{start}-{end} {char}: {password}")?)))
+            .collect(),
+        Dialect::Csv => parse_csv(lines),
+        Dialect::JsonLines => lines
+            .filter(|line| !line.text.is_empty())
+            .map(|line| {
+                let mut error = match serde_json::from_str(line.text) {
+                    Ok(entry) => return Ok((line.number, entry)),
+                    Err(err) => Error::from_json(2, line.text, err),
+                };
+
+                if let Error::Parse { line: number, .. } = &mut error {
+                    *number = line.number;
+                }
+
+                Err(error)
+            })
+            .collect(),
+    }
+}
+
+const CSV_FIELDS: [&str; 4] = ["start", "end", "char", "password"];
+
+fn parse_csv<'a>(lines: impl Iterator<Item = crate::error::Line<'a>>) -> Result<Vec<(usize, Line)>> {
+    let mut lines = lines.filter(|line| !line.text.is_empty()).peekable();
+    let mut columns = [0, 1, 2, 3];
+
+    // A header, unless the first field is already a number.
+    let first = lines.peek().map(csv_fields).transpose()?;
+    let names = first.filter(|fields| fields.first().is_some_and(|(_, name)| name.parse::<usize>().is_err()));
+
+    if let Some(names) = names {
+        let header = lines.next().unwrap();
+
+        for (column, field) in columns.iter_mut().zip(&CSV_FIELDS) {
+            *column = names
+                .iter()
+                .position(|(_, name)| name == field)
+                .ok_or_else(|| header.error(header.text, format!("expected a {:?} column", field)))?;
+        }
+    }
+
+    lines
+        .map(|line| {
+            let fields = csv_fields(&line)?;
+
+            let field = |i: usize| {
+                let end = &line.text[line.text.len()..];
+                fields
+                    .get(columns[i])
+                    .ok_or_else(|| line.error(end, format!("expected a {:?} field", CSV_FIELDS[i])))
+            };
+
+            let (start, end) = (field(0)?, field(1)?);
+            let ((char_token, char), (_, password)) = (field(2)?, field(3)?);
+            let mut chars = char.chars();
+
+            let char = match (chars.next(), chars.next()) {
+                (Some(char), None) => char,
+                _ => return Err(line.error(char_token, "expected a single character")),
+            };
+
+            let entry = Line {
+                start: parse_value(&line, start)?,
+                end: parse_value(&line, end)?,
+                char,
+                password: password.to_string(),
+            };
+
+            Ok((line.number, entry))
+        })
+        .collect()
+}
+
+/// Parses a CSV field, whose raw text is a slice of `line` for pointing errors at.
+fn parse_value(line: &crate::error::Line, (token, value): &(&str, Cow<str>)) -> Result<usize> {
+    value.parse().map_err(|err| line.error(token, format!("invalid usize ({})", err)))
+}
+
+/// Splits a CSV line into its fields, each as its raw text and its value with any
+/// quotes undone.
+fn csv_fields<'a>(line: &crate::error::Line<'a>) -> Result<Vec<(&'a str, Cow<'a, str>)>> {
+    let text = line.text;
+    let mut fields = Vec::new();
+    let mut start = 0;
+
+    loop {
+        let rest = &text[start..];
+
+        let (raw, value) = if let Some(quoted) = rest.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = quoted.char_indices();
+
+            let end = loop {
+                match chars.next() {
+                    Some((i, '"')) if quoted[i + 1..].starts_with('"') => {
+                        value.push('"');
+                        chars.next();
+                    }
+                    Some((i, '"')) => break i + 2,
+                    Some((_, c)) => value.push(c),
+                    None => return Err(line.error(rest, "unterminated quoted field")),
+                }
+            };
+
+            if !rest[end..].is_empty() && !rest[end..].starts_with(',') {
+                return Err(line.error(&rest[end..], "expected a comma after a quoted field"));
+            }
+
+            (&rest[..end], Cow::Owned(value))
+        } else {
+            let raw = &rest[..rest.find(',').unwrap_or(rest.len())];
+            (raw, Cow::Borrowed(raw))
+        };
+
+        fields.push((raw, value));
+        start += raw.len() + 1;
+
+        if start > text.len() {
+            return Ok(fields);
+        }
+    }
+}
+
+/// Random database entries, with passwords leaning towards their policy's character.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let lines: Vec<Line> = (0..size)
+        .map(|_| {
+            let start = rng.range(1..=10) as usize;
+            let end = start + rng.range(1..=8) as usize;
+            let char = (b'a' + rng.below(26) as u8) as char;
+
+            let len = end + rng.range(0..=6) as usize;
+            let password = (0..len)
+                .map(|_| if rng.chance(0.4) { char } else { (b'a' + rng.below(26) as u8) as char })
+                .collect();
+
+            Line { start, end, char, password }
+        })
+        .collect();
+
+    let part1 = lines
+        .iter()
+        .filter(|line| {
+            let count = line.password.matches(line.char).count();
+            count >= line.start && count <= line.end
+        })
+        .count();
+
+    let part2 = lines
+        .iter()
+        .filter(|line| {
+            let chars: Vec<char> = line.password.chars().collect();
+            (chars[line.start - 1] == line.char) != (chars[line.end - 1] == line.char)
+        })
+        .count();
+
+    Synthetic::with_answers(synth::lines(&lines), vec![Some(part1.to_string()), Some(part2.to_string())])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_policies() {
+        let spec =
+            r#"count and (min-length 8 or classes upper,digit) and forbid "a\"b" and regex "^[a-z]+$""#;
+        let policy = parse_policy(spec).unwrap();
+        assert_eq!(policy.to_string(), spec);
+
+        let line: Line = "1-3 a: abcde".parse().unwrap();
+        assert!(Count.allows(&line) && Positions::default().allows(&line));
+        assert_eq!(policy.violations(&line), vec!["(min-length 8 or classes upper,digit)"]);
+
+        let policy = parse_policy("positions and classes upper,lower,digit").unwrap();
+        assert_eq!(policy.violations(&line), vec!["classes upper", "classes digit"]);
+    }
+
+    #[test]
+    fn test_dialects() {
+        let aoc = parse_dialect("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc", Dialect::Aoc).unwrap();
+
+        let csv = "password,char,start,end\n\"abcde\",a,1,3\ncdefg,b,1,3\n\nccccccccc,c,2,9";
+        let lines = |entries: &[(usize, Line)]| entries.iter().map(|(_, line)| line.clone()).collect_vec();
+        assert_eq!(lines(&parse_dialect(csv, Dialect::Csv).unwrap()), lines(&aoc));
+
+        let json = r#"{"start":1,"end":3,"char":"b","password":"cdefg"}"#;
+        assert_eq!(parse_dialect(json, Dialect::JsonLines).unwrap(), vec![(1, aoc[1].1.clone())]);
+
+        let report = audit(&parse_dialect(csv, Dialect::Csv).unwrap(), &Count);
+        assert_eq!((report.allowed, report.failed_lines), (2, vec![3]));
+        assert_eq!(report.failures_by_char.into_iter().collect_vec(), vec![('b', 1)]);
+    }
+
+    #[test]
+    fn test_positions() {
+        // `é` as `e` and a combining accent, then a woman technologist: three code points.
+        let line: Line = "1-3 x: e\u{301}\u{1f469}\u{200d}\u{1f4bb}x".parse().unwrap();
+        let graphemes = Positions { units: Units::Graphemes };
+
+        assert!(!Positions::default().allows(&line));
+        assert!(graphemes.allows(&line));
+        let second = Position::new(2).unwrap().of(&line.password, Units::Graphemes);
+        assert_eq!(second, Some("\u{1f469}\u{200d}\u{1f4bb}"));
+        assert_eq!(parse_policy("positions graphemes").unwrap().to_string(), "positions graphemes");
+
+        let line: Line = "0-1 a: a".parse().unwrap();
+        assert_eq!(Position::new(0), None);
+        assert!(Count.allows(&line) && !graphemes.allows(&line));
+        assert!(part2(&[line]).is_err());
+    }
+}
@@ -0,0 +1,494 @@
+//! Day 20: Jurassic Jigsaw.
+
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    iter::FromIterator,
+};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+
+use crate::{
+    error::{Error, Result},
+    explain,
+    grid::Grid,
+    input::Input,
+    synth::{Rng, Synthetic},
+};
+
+/// A tile's image data.
+pub type TileData = Grid<char>;
+
+/// Maps each tile ID to its image data.
+pub type Tiles = HashMap<u16, TileData>;
+
+#[aoc_generator(day20)]
+pub fn parse(input: &str) -> Result<Tiles> {
+    Input::new(20, input)
+        .sections()
+        .map(|tile| {
+            let (header, pixels) = tile
+                .split_first_line()
+                .ok_or_else(|| Error::unexpected_end(20, tile.first_line(), "a tile header"))?;
+
+            let id = header
+                .text
+                .strip_prefix("Tile ")
+                .and_then(|id| id.strip_suffix(':'))
+                .ok_or_else(|| header.error(header.text, "expected \"Tile <id>:\""))?;
+
+            let id = header.parse_token(id)?;
+
+            let tile = pixels.grid("'.' or '#'", |c| {
+                Some(c).filter(|&c| c == '.' || c == '#')
+            })?;
+
+            if tile.width() != TILE_SIZE + 2 || tile.height() != TILE_SIZE + 2 {
+                let size = TILE_SIZE + 2;
+                return Err(header.error(header.text, format!("expected {}x{} pixels", size, size)));
+            }
+
+            Ok((id, tile))
+        })
+        .collect()
+}
+
+/// Indexes oriented tiles by each of their edges.
+#[derive(Default)]
+pub struct EdgeMap {
+    top: HashMap<u16, Vec<Tile>>,
+    right: HashMap<u16, Vec<Tile>>,
+    bottom: HashMap<u16, Vec<Tile>>,
+    left: HashMap<u16, Vec<Tile>>,
+}
+
+impl EdgeMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, tile: Tile) {
+        self.top.entry(tile.top).or_default().push(tile);
+        self.right.entry(tile.right).or_default().push(tile);
+        self.bottom.entry(tile.bottom).or_default().push(tile);
+        self.left.entry(tile.left).or_default().push(tile);
+    }
+
+    /// The tiles which fit next to `tile` on its `direction` side.
+    pub fn get(&self, tile: Tile, direction: Direction) -> impl Iterator<Item = Tile> + '_ {
+        match direction {
+            Direction::Top => self.bottom.get(&tile.top),
+            Direction::Right => self.left.get(&tile.right),
+            Direction::Bottom => self.top.get(&tile.bottom),
+            Direction::Left => self.right.get(&tile.left),
+        }.into_iter().flatten().copied()
+    }
+}
+
+impl FromIterator<Tile> for EdgeMap {
+    fn from_iter<T: IntoIterator<Item = Tile>>(iter: T) -> Self {
+        let mut edge_map = EdgeMap::new();
+
+        for tile in iter {
+            edge_map.insert(tile);
+        }
+
+        edge_map
+    }
+}
+
+/// A tile in one of its 8 orientations, with its edges encoded as 10 bit integers.
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Default, Debug)]
+pub struct Tile {
+    pub id: u16,
+    pub top: u16,
+    pub right: u16,
+    pub bottom: u16,
+    pub left: u16,
+    pub orientation: u8
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Top,
+    Right,
+    Bottom,
+    Left
+}
+
+pub const DIRECTIONS: [Direction; 4] = [Direction::Top, Direction::Right, Direction::Bottom, Direction::Left];
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Point(pub i8, pub i8);
+
+impl Point {
+    pub fn step(&self, direction: Direction) -> Point {
+        match direction {
+            Direction::Top => Point(self.0, self.1 - 1),
+            Direction::Right => Point(self.0 + 1, self.1),
+            Direction::Bottom => Point(self.0, self.1 + 1),
+            Direction::Left => Point(self.0 - 1, self.1),
+        }
+    }
+}
+
+impl Tile {
+    fn to_int(chars: impl Iterator<Item = char>) -> u16 {
+        chars.fold(0, |i, c| {
+            if c == '#' {
+                (i << 1) | 1
+            } else {
+                i << 1
+            }
+        })
+    }
+
+    /// Only last 10 bits are reversed
+    fn reverse(mut side: u16) -> u16 {
+        side = (side & 0b0000001100001111) << 4 | (side & 0b0000000011110000) >> 4;
+        side = (side & 0b0011000000110011) << 2 | (side & 0b0000000011001100) >> 2;
+        side = (side & 0b0100000001010101) << 1 | (side & 0b1000000010101010) >> 1;
+        side = (side & 0b0000000011111111) << 2 | (side & 0b1100000000000000) >> 14;
+
+        side
+    }
+
+    pub fn new(id: u16, data: &TileData) -> Self {
+        Self {
+            id,
+            top:    Self::to_int(data.row(0).iter().copied()),
+            right:  Self::to_int(data.column(data.width() - 1).copied()),
+            bottom: Self::to_int(data.row(data.height() - 1).iter().copied()),
+            left:   Self::to_int(data.column(0).copied()),
+            orientation: 0,
+        }
+    }
+
+    /// All 8 orientations of this tile, rotations first, then flipped rotations.
+    pub fn permutations(self) -> impl Iterator<Item = Tile> {
+        std::iter::successors(Some(self), |prev| {
+            if prev.orientation == 7 {
+                return None;
+            }
+
+            let mut next = prev.rotated();
+
+            next.orientation += 1;
+
+            if next.orientation == 4 {
+               next = next.flipped();
+            }
+
+            Some(next)
+        })
+    }
+
+    pub fn rotated(&self) -> Self {
+        Self {
+            id: self.id,
+            top: Self::reverse(self.left),
+            right: self.top,
+            bottom: Self::reverse(self.right),
+            left: self.bottom,
+            orientation: self.orientation,
+        }
+    }
+
+    pub fn flipped(&self) -> Self {
+        Self {
+            id: self.id,
+            top: Self::reverse(self.top),
+            right: self.left,
+            bottom: Self::reverse(self.bottom),
+            left: self.right,
+            orientation: self.orientation
+        }
+    }
+}
+
+pub const LAYOUT_SIZE: usize = 12;
+pub const TILE_SIZE: usize = 8;
+pub const IMAGE_SIZE: usize = LAYOUT_SIZE * TILE_SIZE;
+
+pub const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+/// The assembled grid of oriented tiles, indexed by row then column.
+#[derive(Default)]
+pub struct Layout(pub [[Tile; LAYOUT_SIZE]; LAYOUT_SIZE]);
+
+impl From<EdgeMap> for Layout {
+    fn from(edge_map: EdgeMap) -> Self {
+        let mut tile_map = HashMap::new();
+
+        let seed_point = Point(0, 0);
+        let seed_tile = *edge_map.top.values().next().unwrap().first().unwrap();
+
+        tile_map.insert(seed_tile.id, (seed_point, seed_tile));
+
+        let mut stack = vec![(seed_point, seed_tile)];
+        let mut min_point = seed_point;
+
+        while let Some((start_point, start_tile)) = stack.pop() {
+            for (point, tile) in DIRECTIONS
+                .iter()
+                .map(|&direction| (start_point.step(direction), direction))
+                .flat_map(|(point, direction)| edge_map.get(start_tile, direction).map(move |tile| (point, tile)))
+            {
+                if let Entry::Vacant(entry) = tile_map.entry(tile.id) {
+                    entry.insert((point, tile));
+                    stack.push((point, tile));
+                    min_point = min_point.min(point);
+                }
+            }
+        }
+
+        let mut layout = Layout::default();
+
+        for (Point(x, y), tile) in tile_map.values() {
+            layout.0[(y - min_point.1) as usize][(x - min_point.0) as usize] = *tile;
+        }
+
+        layout
+    }
+}
+
+/// The assembled image with tile borders removed.
+pub struct Image(pub Grid<char>);
+
+impl Image {
+    pub fn new(layout: Layout, tiles: &Tiles) -> Self {
+        let mut image = Grid::filled(IMAGE_SIZE, IMAGE_SIZE, '\0');
+
+        for i in 0..LAYOUT_SIZE {
+            for j in 0..LAYOUT_SIZE {
+                let tile = layout.0[i][j];
+                let data = tiles[&tile.id].oriented(tile.orientation);
+
+                for y in 0..TILE_SIZE {
+                    for x in 0..TILE_SIZE {
+                        image[(j * TILE_SIZE + x, i * TILE_SIZE + y)] = data[(x + 1, y + 1)];
+                    }
+                }
+            }
+        }
+
+        Image(image)
+    }
+
+    /// The positions at which every `#` of `key` lands on a `#`.
+    pub fn find_occurences<'a>(&'a self, key: &Grid<char>) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let offsets: Vec<(usize, usize)> = key
+            .positions()
+            .filter(|&pos| key[pos] == '#')
+            .collect();
+
+        self.0.positions().filter(move |&(x, y)| {
+            offsets
+                .iter()
+                .all(|&(dx, dy)| self.0.get((x + dx, y + dy)) == Some(&'#'))
+        })
+    }
+
+    pub fn count_occurences(&self, key: &Grid<char>) -> usize {
+        self.find_occurences(key).count()
+    }
+}
+
+#[aoc(day20, part1)]
+pub fn part1(tiles: &Tiles) -> u64 {
+    let edge_map: EdgeMap = tiles
+        .iter()
+        .flat_map(|(id, data)| Tile::new(*id, data).permutations())
+        .collect();
+
+    let layout = Layout::from(edge_map);
+
+    let max = LAYOUT_SIZE - 1;
+    let corners = [(0, 0), (0, max), (max, 0), (max, max)];
+
+    let ids: Vec<u64> = corners.iter().map(|&(i, j)| layout.0[i][j].id as u64).collect();
+    explain::note(20, "corner tiles", || ids.iter().join(", "));
+
+    ids.iter().product()
+}
+
+#[aoc(day20, part2)]
+pub fn part2(tiles: &Tiles) -> usize {
+    let edge_map: EdgeMap = tiles
+        .iter()
+        .flat_map(|(id, data)| Tile::new(*id, data).permutations())
+        .collect();
+
+    let layout = Layout::from(edge_map);
+    let image = Image::new(layout, tiles);
+
+    let monster = Grid::from_rows(MONSTER.iter().map(|l| l.chars().collect()));
+    let (orientation, monsters) = monster
+        .orientations()
+        .map(|key| image.find_occurences(&key).collect::<Vec<_>>())
+        .enumerate()
+        .find(|(_, monsters)| !monsters.is_empty())
+        .unwrap();
+
+    explain::note(20, "monster orientation", || orientation);
+    explain::note(20, "monsters at", || monsters.iter().map(|(x, y)| format!("({}, {})", x, y)).join(" "));
+
+    let monster_count = monsters.len();
+
+    let monster_chars = MONSTER.iter().flat_map(|l| l.chars()).filter(|&c| c == '#').count();
+    let image_chars = image.0.cells().filter(|&&c| c == '#').count();
+
+    image_chars - monster_count * monster_chars
+}
+
+/// Picks the pixels of every tile edge, `big` being all tiles laid out with
+/// neighbors sharing their edge. Edges are never palindromes, and no two edges
+/// match each other either way round, so that tiles only fit in one place.
+/// Returns `false` if it ran out of edges.
+fn synthesize_edges(rng: &mut Rng, big: &mut Grid<char>) -> bool {
+    let step = TILE_SIZE + 1;
+    let pixel = |rng: &mut Rng| if rng.chance(0.5) { '#' } else { '.' };
+
+    for y in (0..big.height()).step_by(step) {
+        for x in (0..big.width()).step_by(step) {
+            big[(x, y)] = pixel(rng);
+        }
+    }
+
+    let mut used = HashSet::new();
+
+    for i in 0..=LAYOUT_SIZE {
+        for j in 0..LAYOUT_SIZE {
+            for &horizontal in &[true, false] {
+                let position = |k: usize| {
+                    if horizontal {
+                        (j * step + k, i * step)
+                    } else {
+                        (i * step, j * step + k)
+                    }
+                };
+
+                let found = (0..1000).any(|_| {
+                    for k in 1..step {
+                        big[position(k)] = pixel(rng);
+                    }
+
+                    let edge = Tile::to_int((0..=step).map(|k| big[position(k)]));
+                    let reversed = Tile::reverse(edge);
+
+                    edge != reversed && !used.contains(&edge) && !used.contains(&reversed)
+                });
+
+                if !found {
+                    return false;
+                }
+
+                used.insert(Tile::to_int((0..=step).map(|k| big[position(k)])));
+            }
+        }
+    }
+
+    true
+}
+
+/// An image full of sea monsters all facing the same way, cut into tiles which
+/// are each randomly rotated or flipped. The layout is always 12 by 12 tiles, so
+/// `size` is unused.
+pub fn synthesize(rng: &mut Rng, _size: usize) -> Synthetic {
+    let monster = Grid::from_rows(MONSTER.iter().map(|l| l.chars().collect()));
+    let key = monster.oriented(rng.below(8) as u8);
+    let offsets: Vec<(usize, usize)> = key.positions().filter(|&pos| key[pos] == '#').collect();
+
+    let (image, monsters) = loop {
+        let mut image = Grid::new(
+            IMAGE_SIZE,
+            IMAGE_SIZE,
+            (0..IMAGE_SIZE * IMAGE_SIZE).map(|_| if rng.chance(0.3) { '#' } else { '.' }).collect(),
+        );
+
+        let mut taken = Grid::filled(IMAGE_SIZE, IMAGE_SIZE, false);
+
+        for _ in 0..rng.range(10..=40) {
+            let x = rng.index(IMAGE_SIZE - key.width() + 1);
+            let y = rng.index(IMAGE_SIZE - key.height() + 1);
+
+            if key.positions().any(|(dx, dy)| taken[(x + dx, y + dy)]) {
+                continue;
+            }
+
+            for (dx, dy) in key.positions() {
+                taken[(x + dx, y + dy)] = true;
+            }
+
+            for &(dx, dy) in &offsets {
+                image[(x + dx, y + dy)] = '#';
+            }
+        }
+
+        let image = Image(image);
+        let counts: Vec<usize> = monster.orientations().map(|key| image.count_occurences(&key)).collect();
+
+        if counts.iter().filter(|&&count| count > 0).count() == 1 {
+            break (image, counts.into_iter().sum::<usize>());
+        }
+    };
+
+    let step = TILE_SIZE + 1;
+    let mut big = Grid::filled(LAYOUT_SIZE * step + 1, LAYOUT_SIZE * step + 1, '.');
+
+    while !synthesize_edges(rng, &mut big) {}
+
+    for (x, y) in image.0.positions() {
+        let to_big = |n: usize| n / TILE_SIZE * step + 1 + n % TILE_SIZE;
+        big[(to_big(x), to_big(y))] = image.0[(x, y)];
+    }
+
+    let mut ids: Vec<u16> = (1000..10000).collect();
+    rng.shuffle(&mut ids);
+
+    let mut tiles: Vec<(u16, TileData)> = (0..LAYOUT_SIZE * LAYOUT_SIZE)
+        .map(|n| {
+            let (i, j) = (n / LAYOUT_SIZE, n % LAYOUT_SIZE);
+            let data = Grid::from_rows(
+                (0..=step).map(|y| (0..=step).map(|x| big[(j * step + x, i * step + y)]).collect()),
+            );
+
+            (ids[n], data.oriented(rng.below(8) as u8))
+        })
+        .collect();
+
+    let max = LAYOUT_SIZE - 1;
+    let corners: u64 = [0, max, max * LAYOUT_SIZE, max * LAYOUT_SIZE + max]
+        .iter()
+        .map(|&n| ids[n] as u64)
+        .product();
+
+    let monster_chars = offsets.len();
+    let roughness = image.0.cells().filter(|&&c| c == '#').count() - monsters * monster_chars;
+
+    rng.shuffle(&mut tiles);
+
+    let input = tiles
+        .iter()
+        .map(|(id, data)| format!("Tile {}:\n{}", id, data.to_string().trim_end()))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    Synthetic::with_answers(input, vec![Some(corners.to_string()), Some(roughness.to_string())])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bit_reverse() {
+        assert_eq!(0b1111100000, Tile::reverse(0b0000011111));
+        assert_eq!(0b1010101010, Tile::reverse(0b0101010101));
+        assert_eq!(0b0110100101, Tile::reverse(0b1010010110));
+    }
+}
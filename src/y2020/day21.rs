@@ -0,0 +1,197 @@
+//! Day 21: Allergen Assessment.
+
+use std::collections::{HashMap, HashSet};
+
+use pest_derive::Parser;
+use pest::{Parser, iterators::Pair};
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+
+use crate::{
+    error::{Error, Result},
+    explain,
+    input::Input,
+    synth::{Rng, Synthetic},
+};
+
+#[derive(Parser)]
+#[grammar = "y2020/grammars/day21.pest"]
+struct FoodParser;
+
+fn collect(pair: Pair<Rule>) -> Vec<String> {
+    pair
+        .into_inner()
+        .map(|p| p.as_str().to_string())
+        .collect()
+}
+
+/// A food's ingredients and some of the allergens it contains.
+pub type Food = (Vec<String>, Vec<String>);
+
+#[aoc_generator(day21)]
+pub fn parse(input: &str) -> Result<Vec<Food>> {
+    let mut result = Vec::new();
+
+    let input = Input::new(21, input);
+    let foods = FoodParser::parse(Rule::all, input.text())
+        .map_err(|err| Error::from_pest(21, 1, input.text(), err))?;
+
+    for food in foods {
+        if matches!(food.as_rule(), Rule::EOI) {
+            break
+        }
+
+        let (ingredients, allergens) = food.into_inner().next_tuple().unwrap();
+
+        result.push((collect(ingredients), collect(allergens)))
+    }
+
+    Ok(result)
+}
+
+/// Maps each allergen to the only ingredient which can contain it.
+pub fn find_allergens(food: &[Food]) -> HashMap<&String, &String> {
+    let mut potential: HashMap<&String, HashSet<&String>> = HashMap::new();
+
+    for (ingredients, allergens) in food {
+        let ingredients: HashSet<&String> = ingredients.iter().collect();
+
+        for allergen in allergens {
+            potential.entry(allergen).and_modify(|set| *set = &*set & &ingredients ).or_insert_with(|| ingredients.clone());
+        }
+    }
+
+    let mut allergen_map = HashMap::new();
+
+    while let Some((&allergen, set)) = potential.iter().find(|(_, set)| set.len() == 1) {
+        let ingredient = *set.iter().exactly_one().unwrap();
+        allergen_map.insert(allergen, ingredient);
+        potential.values_mut().for_each(|set| { set.remove(ingredient); });
+    }
+
+    for (allergen, ingredient) in allergen_map.iter().sorted() {
+        explain::note(21, allergen, || ingredient);
+    }
+
+    allergen_map
+}
+
+#[aoc(day21, part1)]
+pub fn part1(food: &[Food]) -> usize {
+    let allergen_map = find_allergens(food);
+    let ingredients: HashSet<_> = allergen_map.values().collect();
+
+    food
+        .iter()
+        .flat_map(|(ingredients, _)| ingredients)
+        .filter(|ingredient| !ingredients.contains(&ingredient))
+        .count()
+}
+
+#[aoc(day21, part2)]
+pub fn part2(food: &[Food]) -> String {
+    let allergen_map = find_allergens(food);
+
+    allergen_map
+        .iter()
+        .sorted()
+        .map(|(_, ingredient)| ingredient)
+        .join(",")
+}
+
+const ALLERGENS: [&str; 8] = ["dairy", "eggs", "fish", "nuts", "peanuts", "sesame", "shellfish", "soy"];
+
+/// At least `size` foods, each listing some of the allergens it contains.
+///
+/// Every allergen is in exactly one ingredient, which is always in the foods listing
+/// it. Foods are added until every allergen can be narrowed down to its ingredient.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let allergens = &ALLERGENS[..rng.range(3..=ALLERGENS.len() as i64) as usize];
+
+    let mut words = HashSet::new();
+
+    while words.len() < allergens.len() * 8 {
+        let len = rng.range(4..=8) as usize;
+        words.insert(rng.word(len));
+    }
+
+    let mut words: Vec<String> = words.into_iter().sorted().collect();
+    rng.shuffle(&mut words);
+
+    let (dangerous, safe) = words.split_at(allergens.len());
+    let mut foods: Vec<(Vec<&String>, Vec<&str>)> = Vec::new();
+
+    let resolved = |foods: &[(Vec<&String>, Vec<&str>)]| {
+        let candidates: Option<Vec<HashSet<&String>>> = allergens
+            .iter()
+            .map(|allergen| {
+                foods
+                    .iter()
+                    .filter(|(_, listed)| listed.contains(allergen))
+                    .map(|(ingredients, _)| ingredients.iter().copied().collect::<HashSet<_>>())
+                    .fold1(|a, b| &a & &b)
+            })
+            .collect();
+
+        let mut candidates = match candidates {
+            Some(candidates) => candidates,
+            None => return false,
+        };
+
+        while let Some(i) = candidates.iter().position(|set| set.len() == 1) {
+            let ingredient = *candidates[i].iter().next().unwrap();
+
+            if ingredient != &dangerous[i] {
+                return false;
+            }
+
+            for set in &mut candidates {
+                set.remove(ingredient);
+            }
+        }
+
+        candidates.iter().all(HashSet::is_empty)
+    };
+
+    while foods.len() < size.max(1) || !resolved(&foods) {
+        let mut listed: Vec<&str> = allergens.iter().copied().filter(|_| rng.chance(0.3)).collect();
+
+        if listed.is_empty() {
+            listed.push(*rng.choose(allergens));
+        }
+
+        let mut ingredients: Vec<&String> = dangerous
+            .iter()
+            .zip(allergens)
+            .filter(|(_, allergen)| listed.contains(allergen) || rng.chance(0.2))
+            .map(|(ingredient, _)| ingredient)
+            .collect();
+
+        ingredients.extend(safe.iter().filter(|_| rng.chance(0.3)));
+
+        rng.shuffle(&mut ingredients);
+        foods.push((ingredients, listed));
+    }
+
+    let part1 = foods
+        .iter()
+        .flat_map(|(ingredients, _)| ingredients)
+        .filter(|ingredient| !dangerous.contains(ingredient))
+        .count();
+
+    let part2 = allergens
+        .iter()
+        .zip(dangerous)
+        .sorted()
+        .map(|(_, ingredient)| ingredient)
+        .join(",");
+
+    let input = foods
+        .iter()
+        .map(|(ingredients, listed)| {
+            format!("{} (contains {})", ingredients.iter().join(" "), listed.join(", "))
+        })
+        .join("\n");
+
+    Synthetic::with_answers(input, vec![Some(part1.to_string()), Some(part2)])
+}
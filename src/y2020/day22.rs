@@ -0,0 +1,247 @@
+//! Day 22: Crab Combat.
+
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use fnv::{FnvHasher, FnvHashSet};
+
+use crate::{
+    error::{Error, Result},
+    input::Input,
+    limits::Checkpoint,
+    synth::{self, Rng, Synthetic},
+};
+
+/// The starting decks of player 1 and player 2, top card first.
+pub type Decks = (VecDeque<u8>, VecDeque<u8>);
+
+#[aoc_generator(day22)]
+pub fn parse(input: &str) -> Result<Decks> {
+    let input = Input::new(22, input);
+    let last_line = input.section().last_line();
+    let mut sections = input.sections();
+
+    let mut parse_deck = |player: &str| {
+        let header = format!("Player {}:", player);
+
+        let (first, deck) = sections
+            .next()
+            .and_then(|section| section.split_first_line())
+            .ok_or_else(|| Error::unexpected_end(22, last_line, &header))?;
+
+        if first.text != header {
+            return Err(first.error(first.text, format!("expected {:?}", header)));
+        }
+
+        deck.lines().map(|line| line.parse()).collect::<Result<VecDeque<u8>>>()
+    };
+
+    Ok((parse_deck("1")?, parse_deck("2")?))
+}
+
+/// Sums each card multiplied by its position from the bottom of the deck.
+pub fn score(deck: VecDeque<u8>) -> u64 {
+    deck.into_iter()
+        .rev()
+        .zip(1..)
+        .map(|(c, i)| (c as u64) * i)
+        .sum()
+}
+
+/// Gives up if the solver runs out of budget, as it does when the game never ends.
+#[aoc(day22, part1)]
+pub fn part1(decks: &Decks) -> Result<u64> {
+    let (mut p1, mut p2) = decks.clone();
+    let mut checkpoint = Checkpoint::new(22);
+
+    while !p1.is_empty() && !p2.is_empty() {
+        checkpoint.tick()?;
+
+        let (c1, c2) = (p1.pop_front().unwrap(), p2.pop_front().unwrap());
+
+        if c1 > c2 {
+            p1.push_back(c1);
+            p1.push_back(c2);
+        } else {
+            p2.push_back(c2);
+            p2.push_back(c1);
+        }
+    }
+
+    let winner = if p1.is_empty() { p2 } else { p1 };
+
+    Ok(score(winner))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Winner {
+    P1,
+    P2,
+}
+
+/// Plays a game of Recursive Combat, leaving the final decks in `p1` and `p2`. Ticks
+/// `checkpoint` every round.
+pub fn recursive_combat(
+    p1: &mut VecDeque<u8>,
+    p2: &mut VecDeque<u8>,
+    checkpoint: &mut Checkpoint,
+) -> Result<Winner> {
+    let mut seen = FnvHashSet::default();
+
+    while !p1.is_empty() && !p2.is_empty() {
+        checkpoint.tick()?;
+
+        let mut hasher = FnvHasher::default();
+        p1.hash(&mut hasher);
+        p2.hash(&mut hasher);
+
+        // Collisions are extremely unlikely
+        if !seen.insert(hasher.finish()) {
+            return Ok(Winner::P1);
+        }
+
+        let (c1, c2) = (p1.pop_front().unwrap(), p2.pop_front().unwrap());
+
+        if p1.len() >= c1 as usize && p2.len() >= c2 as usize {
+            // Hypothesis: the deck with the largest card wins, or there is a cycle.
+            // Base case: If not enough cards remain to do a recursive step, then the
+            //      largest card can never be stolen. Thus, either the player with the
+            //      largest card wins, or there is a cycle.
+            // Inductive step: If the largest card remains in the deck for the recursive
+            //      step, then by the inductive hypothesis either the player with said
+            //      card wins, or there is a cycle. If the largest card is drawn, since
+            //      all numbers are positive and there are no duplicates, it is impossible
+            //      for enough cards to remain in the deck to do a recursive step. Thus,
+            //      we are in the base case.
+            //
+            // Since a cycle results in a win for player 1, if player 1 has the largest
+            // card, he will win the subgame.
+
+            let p1_iter = p1.iter().copied().take(c1 as usize);
+            let p2_iter = p2.iter().copied().take(c2 as usize);
+
+            if p1_iter.clone().max() > p2_iter.clone().max() {
+                p1.push_back(c1);
+                p1.push_back(c2);
+            } else {
+                match recursive_combat(&mut p1_iter.collect(), &mut p2_iter.collect(), checkpoint)? {
+                    Winner::P1 => {
+                        p1.push_back(c1);
+                        p1.push_back(c2);
+                    }
+                    Winner::P2 => {
+                        p2.push_back(c2);
+                        p2.push_back(c1);
+                    }
+                }
+            }
+        } else if c1 > c2 {
+            p1.push_back(c1);
+            p1.push_back(c2);
+        } else {
+            p2.push_back(c2);
+            p2.push_back(c1);
+        }
+    }
+
+    Ok(if p1.is_empty() {
+        Winner::P2
+    } else {
+        Winner::P1
+    })
+}
+
+#[aoc(day22, part2)]
+pub fn part2(decks: &Decks) -> Result<u64> {
+    let (mut p1, mut p2) = decks.clone();
+    let mut checkpoint = Checkpoint::new(22);
+
+    Ok(match recursive_combat(&mut p1, &mut p2, &mut checkpoint)? {
+        Winner::P1 => score(p1),
+        Winner::P2 => score(p2),
+    })
+}
+
+/// [`recursive_combat`] by the rules alone: plays out every subgame, and remembers
+/// whole decks rather than their hashes. This takes exponential time on large decks,
+/// so it ticks `checkpoint` every round.
+pub fn recursive_combat_reference(
+    p1: &mut VecDeque<u8>,
+    p2: &mut VecDeque<u8>,
+    checkpoint: &mut Checkpoint,
+) -> Result<Winner> {
+    let mut seen = HashSet::new();
+
+    while !p1.is_empty() && !p2.is_empty() {
+        checkpoint.tick()?;
+
+        if !seen.insert((p1.clone(), p2.clone())) {
+            return Ok(Winner::P1);
+        }
+
+        let (c1, c2) = (p1.pop_front().unwrap(), p2.pop_front().unwrap());
+
+        let winner = if p1.len() >= c1 as usize && p2.len() >= c2 as usize {
+            recursive_combat_reference(
+                &mut p1.iter().copied().take(c1 as usize).collect(),
+                &mut p2.iter().copied().take(c2 as usize).collect(),
+                checkpoint,
+            )?
+        } else if c1 > c2 {
+            Winner::P1
+        } else {
+            Winner::P2
+        };
+
+        match winner {
+            Winner::P1 => p1.extend([c1, c2]),
+            Winner::P2 => p2.extend([c2, c1]),
+        }
+    }
+
+    Ok(if p1.is_empty() { Winner::P2 } else { Winner::P1 })
+}
+
+pub fn part2_reference(decks: &Decks) -> Result<u64> {
+    let (mut p1, mut p2) = decks.clone();
+    let mut checkpoint = Checkpoint::new(22);
+
+    Ok(match recursive_combat_reference(&mut p1, &mut p2, &mut checkpoint)? {
+        Winner::P1 => score(p1),
+        Winner::P2 => score(p2),
+    })
+}
+
+/// Two decks splitting the cards `1..=2 * size` (up to 50 cards each). Deals for
+/// which a plain game of Combat would not end within 100000 rounds are redealt.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let per_player = size.clamp(2, 50) as u8;
+
+    let cards = loop {
+        let mut cards: Vec<u8> = (1..=2 * per_player).collect();
+        rng.shuffle(&mut cards);
+
+        let (mut p1, mut p2): Decks = (
+            cards[..per_player as usize].iter().copied().collect(),
+            cards[per_player as usize..].iter().copied().collect(),
+        );
+
+        for _ in 0..100_000 {
+            match (p1.pop_front(), p2.pop_front()) {
+                (Some(c1), Some(c2)) if c1 > c2 => p1.extend([c1, c2]),
+                (Some(c1), Some(c2)) => p2.extend([c2, c1]),
+                _ => break,
+            }
+        }
+
+        if p1.is_empty() || p2.is_empty() {
+            break cards;
+        }
+    };
+
+    let deck = |cards: &[u8]| synth::lines(cards);
+    let (p1, p2) = cards.split_at(per_player as usize);
+
+    Synthetic::new(format!("Player 1:\n{}\n\nPlayer 2:\n{}", deck(p1), deck(p2)))
+}
@@ -0,0 +1,212 @@
+//! Day 23: Crab Cups.
+
+use std::iter::FromIterator;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::{
+    error::{Error, Result},
+    frames::{Frame, Recorder},
+    grid::Grid,
+    input::Input,
+    limits::Checkpoint,
+    synth::{Rng, Synthetic},
+};
+
+#[aoc_generator(day23)]
+pub fn parse(input: &str) -> Result<Vec<usize>> {
+    let input = Input::new(23, input);
+    let line = input
+        .lines()
+        .next()
+        .ok_or_else(|| Error::unexpected_end(23, 1, "the cup labels"))?;
+
+    if line.text.is_empty() {
+        return Err(Error::unexpected_end(23, 1, "the cup labels"));
+    }
+
+    line.map_chars("a digit from 1 to 9", |c| {
+        c.to_digit(10).filter(|&d| d > 0).map(|d| d as usize)
+    })
+}
+
+#[aoc(day23, part1)]
+pub fn part1(cups: &[usize]) -> Result<usize> {
+    part1_traced(cups, &mut Recorder::disabled())
+}
+
+pub fn part1_traced(cups: &[usize], recorder: &mut Recorder) -> Result<usize> {
+    let mut crab_game: CrabGame = cups.iter().copied().collect();
+
+    crab_game.play_traced(100, recorder)?;
+
+    Ok(crab_game.iter_after(1).fold(0, |acc, cup| acc * 10 + cup))
+}
+
+#[aoc(day23, part2)]
+pub fn part2(cups: &[usize]) -> Result<usize> {
+    part2_traced(cups, &mut Recorder::disabled())
+}
+
+pub fn part2_traced(cups: &[usize], recorder: &mut Recorder) -> Result<usize> {
+    let mut crab_game: CrabGame = cups.iter().copied().chain(10..=1_000_000).collect();
+
+    crab_game.play_traced(10_000_000, recorder)?;
+
+    Ok(crab_game.iter_after(1).take(2).product())
+}
+
+/// A circle of cups stored as a successor table: `cups[c]` is the cup after `c`.
+pub struct CrabGame {
+    cups: Vec<usize>,
+    cur: usize,
+    min: usize,
+    max: usize,
+}
+
+impl FromIterator<usize> for CrabGame {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut cups_iter = iter.into_iter();
+        let mut cups = vec![0; cups_iter.size_hint().0];
+
+        let first = cups_iter.next().unwrap();
+        let mut last = first;
+
+        let mut min = last;
+        let mut max = last;
+
+        for cup in cups_iter {
+            if last >= cups.len() {
+                cups.extend((last..=cups.len()).map(|_| 0))
+            }
+            min = min.min(cup);
+            max = max.max(cup);
+            cups[last] = cup;
+            last = cup;
+        }
+
+        if last >= cups.len() {
+            cups.extend((last..=cups.len()).map(|_| 0))
+        }
+
+        cups[last] = first;
+
+        Self {
+            cups,
+            cur: first,
+            min,
+            max
+        }
+    }
+}
+
+impl CrabGame {
+    fn remove_after(&mut self, after: usize) -> usize {
+        let remove = self.cups[after];
+        self.cups[after] = self.cups[remove];
+        remove
+    }
+
+    fn insert_after(&mut self, after: usize, new: usize) {
+        self.cups[new] = self.cups[after];
+        self.cups[after] = new;
+    }
+
+    /// Iterates clockwise over every cup after `after`.
+    pub fn iter_after(&self, after: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(Some(self.cups[after]), move |&prev| {
+            let next = self.cups[prev];
+            if next == after {
+                None
+            } else {
+                Some(next)
+            }
+        })
+    }
+
+    pub fn round(&mut self) {
+        let c1 = self.remove_after(self.cur);
+        let c2 = self.remove_after(self.cur);
+        let c3 = self.remove_after(self.cur);
+
+        let mut dst = self.cur;
+        while [self.cur, c1, c2, c3].contains(&dst) {
+            if dst == self.min {
+                dst = self.max;
+            } else {
+                dst -= 1;
+            }
+        }
+
+        self.insert_after(dst, c3);
+        self.insert_after(dst, c2);
+        self.insert_after(dst, c1);
+
+        self.cur = self.cups[self.cur];
+    }
+
+    /// Plays `rounds` rounds, giving up if the solver runs out of budget.
+    pub fn play(&mut self, rounds: usize) -> Result<()> {
+        let mut checkpoint = Checkpoint::new(23).with_total(rounds as u64);
+
+        for _ in 0..rounds {
+            self.round();
+            checkpoint.tick()?;
+        }
+
+        Ok(())
+    }
+
+    /// Like [`CrabGame::play`], but offers a frame before the first round and after each one.
+    pub fn play_traced(&mut self, rounds: usize, recorder: &mut Recorder) -> Result<()> {
+        if !recorder.is_enabled() {
+            return self.play(rounds);
+        }
+
+        let mut checkpoint = Checkpoint::new(23).with_total(rounds as u64);
+
+        recorder.record(|| self.render());
+
+        for _ in 0..rounds {
+            self.round();
+            recorder.record(|| self.render());
+            checkpoint.tick()?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws up to the first 64 cups from the current one as bars as tall as their
+    /// labels, with the current cup brightest.
+    pub fn render(&self) -> Frame {
+        const WIDTH: usize = 64;
+        const HEIGHT: usize = 32;
+
+        let cups: Vec<usize> = std::iter::once(self.cur)
+            .chain(self.iter_after(self.cur))
+            .take(WIDTH)
+            .collect();
+
+        let mut frame = Grid::filled(cups.len(), HEIGHT, 0);
+
+        for (x, &cup) in cups.iter().enumerate() {
+            let height = (cup * HEIGHT).div_ceil(self.max).min(HEIGHT);
+            let value = if x == 0 { 255 } else { 160 };
+
+            for y in (HEIGHT - height)..HEIGHT {
+                frame[(x, y)] = value;
+            }
+        }
+
+        frame
+    }
+}
+
+/// A random order of the cups labelled 1 to 9. The puzzle always has nine cups, so
+/// `size` is unused.
+pub fn synthesize(rng: &mut Rng, _size: usize) -> Synthetic {
+    let mut cups: Vec<usize> = (1..=9).collect();
+    rng.shuffle(&mut cups);
+
+    Synthetic::new(cups.iter().map(usize::to_string).collect())
+}
@@ -0,0 +1,215 @@
+//! Day 24: Lobby Layout.
+
+use std::collections::HashSet;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    automaton::{Automaton, Hex, Mode},
+    error::Result,
+    frames::{self, Frame, Recorder},
+    input::Input,
+    limits::Checkpoint,
+    synth::{self, Rng, Synthetic},
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+    NE
+}
+
+pub const DIRECTIONS: [Direction; 6] = [Direction::E, Direction::SE, Direction::SW, Direction::W, Direction::NW, Direction::NE];
+
+/// Cube coordinates of a hexagonal tile, where `x + y + z == 0`.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Coordinate {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Coordinate {
+    pub fn step(&self, direction: Direction) -> Self {
+        match direction {
+            Direction::E => Coordinate {
+                x: self.x + 1,
+                y: self.y - 1,
+                z: self.z
+            },
+            Direction::SE => Coordinate {
+                x: self.x,
+                y: self.y - 1,
+                z: self.z + 1
+            },
+            Direction::SW => Coordinate {
+                x: self.x - 1,
+                y: self.y,
+                z: self.z + 1
+            },
+            Direction::W => Coordinate {
+                x: self.x - 1,
+                y: self.y + 1,
+                z: self.z
+            },
+            Direction::NW => Coordinate {
+                x: self.x,
+                y: self.y + 1,
+                z: self.z - 1
+            },
+            Direction::NE => Coordinate {
+                x: self.x + 1,
+                y: self.y,
+                z: self.z - 1
+            },
+        }
+    }
+}
+
+/// A white tile with 2 black neighbors flips to black, and a black tile with 0
+/// or more than 2 flips to white.
+pub const RULE: &str = "B2/S12";
+pub const DAYS: usize = 100;
+
+/// The lobby floor, as the set of tiles flipped to black.
+pub struct Floor {
+    pub black_tiles: HashSet<Coordinate>,
+}
+
+impl Floor {
+    /// Flips the tile at the end of each path.
+    pub fn new(tiles: &[Vec<Direction>]) -> Self {
+        let mut black_tiles = HashSet::new();
+
+        for tile in tiles {
+            let mut coord = Coordinate::default();
+
+            for &direction in tile {
+                coord = coord.step(direction);
+            }
+
+            if !black_tiles.remove(&coord) {
+                black_tiles.insert(coord);
+            }
+        }
+
+        Self { black_tiles }
+    }
+
+    /// Runs the daily flipping for `days` days and counts the black tiles. Gives up if
+    /// the solver runs out of budget first.
+    pub fn run(&self, days: usize, recorder: &mut Recorder) -> Result<usize> {
+        let black = self.black_tiles.iter().map(|c| (c.x, c.y, c.z));
+        let mut automaton = Automaton::new(Hex, RULE.parse().expect("invalid rule"), black);
+
+        let mut checkpoint = Checkpoint::new(24).with_total(days as u64).with_stride(1);
+
+        automaton.run_checked(Mode::Generations(days), &mut checkpoint, |automaton| {
+            recorder.record(|| render(automaton.live()))
+        })?;
+
+        Ok(automaton.population())
+    }
+}
+
+/// Draws black tiles as bright pixels, shifting every other row by half a tile.
+pub fn render<'a>(black_tiles: impl IntoIterator<Item = &'a (i64, i64, i64)>) -> Frame {
+    frames::render_points(black_tiles.into_iter().map(|&(x, _, z)| {
+        let column = 2 * x + z;
+
+        ((column, z), 255)
+    }))
+}
+
+#[aoc_generator(day24)]
+pub fn parse(input: &str) -> Result<Vec<Vec<Direction>>> {
+    Input::new(24, input)
+        .lines()
+        .map(|line| {
+            let mut result = Vec::new();
+            let mut rest = line.text;
+
+            while !rest.is_empty() {
+                let (dir, len) = match rest.as_bytes() {
+                    [b'e', ..] => (Direction::E, 1),
+                    [b's', b'e', ..] => (Direction::SE, 2),
+                    [b's', b'w', ..] => (Direction::SW, 2),
+                    [b'w', ..] => (Direction::W, 1),
+                    [b'n', b'w', ..] => (Direction::NW, 2),
+                    [b'n', b'e', ..] => (Direction::NE, 2),
+                    _ => return Err(line.error(rest, "expected one of e, se, sw, w, nw or ne")),
+                };
+
+                result.push(dir);
+                rest = &rest[len..];
+            }
+
+            Ok(result)
+        })
+        .collect()
+}
+
+#[aoc(day24, part1)]
+pub fn part1(tiles: &[Vec<Direction>]) -> usize {
+    part1_traced(tiles, &mut Recorder::disabled())
+}
+
+pub fn part1_traced(tiles: &[Vec<Direction>], recorder: &mut Recorder) -> usize {
+    let floor = Floor::new(tiles);
+    let black: Vec<_> = floor.black_tiles.iter().map(|c| (c.x, c.y, c.z)).collect();
+
+    recorder.record(|| render(&black));
+
+    floor.black_tiles.len()
+}
+
+#[aoc(day24, part2)]
+pub fn part2(tiles: &[Vec<Direction>]) -> Result<usize> {
+    part2_traced(tiles, &mut Recorder::disabled())
+}
+
+pub fn part2_traced(tiles: &[Vec<Direction>], recorder: &mut Recorder) -> Result<usize> {
+    Floor::new(tiles).run(DAYS, recorder)
+}
+
+/// `size` paths of 5 to 25 steps from the reference tile.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    const STEPS: [(&str, (i64, i64)); 6] = [
+        ("e", (1, 0)),
+        ("se", (0, 1)),
+        ("sw", (-1, 1)),
+        ("w", (-1, 0)),
+        ("nw", (0, -1)),
+        ("ne", (1, -1)),
+    ];
+
+    let mut black = HashSet::new();
+
+    let paths: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let mut path = String::new();
+            let mut tile = (0, 0);
+
+            for _ in 0..rng.range(5..=25) {
+                let (step, (dq, dr)) = *rng.choose(&STEPS);
+
+                path.push_str(step);
+                tile = (tile.0 + dq, tile.1 + dr);
+            }
+
+            if !black.insert(tile) {
+                black.remove(&tile);
+            }
+
+            path
+        })
+        .collect();
+
+    Synthetic::with_answers(synth::lines(paths), vec![Some(black.len().to_string()), None])
+}
@@ -0,0 +1,83 @@
+//! Day 25: Combo Breaker.
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::{
+    error::{Error, Result},
+    input::Input,
+    limits::Checkpoint,
+    synth::{Rng, Synthetic},
+};
+
+/// The card's and the door's public keys.
+#[aoc_generator(day25)]
+pub fn parse(input: &str) -> Result<(u64, u64)> {
+    let input = Input::new(25, input);
+    let mut lines = input.lines();
+
+    let mut next_key = |number, name| {
+        let line = lines.next().ok_or_else(|| Error::unexpected_end(25, number, name))?;
+        let key = line.parse()?;
+
+        if key >= MODULO {
+            return Err(line.error(line.text, format!("expected a key below {}", MODULO)));
+        }
+
+        Ok(key)
+    };
+
+    Ok((next_key(1, "the card's public key")?, next_key(2, "the door's public key")?))
+}
+
+pub const SUBJECT_NUMBER: u64 = 7;
+pub const MODULO: u64 = 20201227;
+
+#[aoc(day25, part1)]
+pub fn part1(&(pub1, pub2): &(u64, u64)) -> Result<u64> {
+    let mut checkpoint = Checkpoint::new(25);
+    let mut value = 1;
+
+    // The values cycle after at most `MODULO - 1` steps.
+    for loop_size in 0..MODULO {
+        if value == pub1 {
+            return Ok(mod_pow(pub2, loop_size));
+        }
+
+        value = (value * SUBJECT_NUMBER) % MODULO;
+        checkpoint.tick()?;
+    }
+
+    Err(checkpoint.give_up(format!("no loop size transforms {} into {}", SUBJECT_NUMBER, pub1)))
+}
+
+/// `base ^ exponent` modulo [`MODULO`].
+fn mod_pow(mut base: u64, mut exponent: u64) -> u64 {
+    let mut result = 1;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % MODULO;
+        }
+
+        base = base * base % MODULO;
+        exponent >>= 1;
+    }
+
+    result
+}
+
+/// Public keys from secret loop sizes of up to `size * 100000`, capped at the
+/// puzzle's eight digits.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let max = (size.max(1) as i64 * 100_000).min(MODULO as i64 - 2);
+    let card_loop_size = rng.range(1..=max) as u64;
+    let door_loop_size = rng.range(1..=max) as u64;
+
+    let card_key = mod_pow(SUBJECT_NUMBER, card_loop_size);
+    let door_key = mod_pow(SUBJECT_NUMBER, door_loop_size);
+
+    Synthetic::with_answers(
+        format!("{}\n{}", card_key, door_key),
+        vec![Some(mod_pow(door_key, card_loop_size).to_string())],
+    )
+}
@@ -0,0 +1,90 @@
+//! Day 3: Toboggan Trajectory.
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Result,
+    grid::Grid,
+    input::Input,
+    synth::{self, Rng, Synthetic},
+};
+
+pub const SLOPE_P1: (usize, usize) = (3, 1);
+pub const SLOPES_P2: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+/// A slope of trees which repeats infinitely to the right.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Map {
+    trees: Grid<bool>,
+}
+
+impl Map {
+    pub fn new(trees: Grid<bool>) -> Self {
+        Self { trees }
+    }
+
+    pub fn is_tree(&self, x: usize, y: usize) -> bool {
+        *self.trees.get_wrapping((x as isize, y as isize))
+    }
+
+    pub fn height(&self) -> usize {
+        self.trees.height()
+    }
+
+    /// Counts the trees hit going down the slope `(right, down)` from the top left.
+    pub fn trees_hit(&self, (mx, my): (usize, usize)) -> usize {
+        std::iter::successors(Some((0, 0)), |(x, y)| Some((x + mx, y + my)))
+            .take_while(|&(_, y)| y < self.height())
+            .filter(|&(x, y)| self.is_tree(x, y))
+            .count()
+    }
+}
+
+#[aoc_generator(day3)]
+pub fn parse(input: &str) -> Result<Map> {
+    let trees = Input::new(3, input).grid("'.' or '#'", |c| match c {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    })?;
+
+    Ok(Map::new(trees))
+}
+
+#[aoc(day3, part1)]
+pub fn part1(map: &Map) -> usize {
+    map.trees_hit(SLOPE_P1)
+}
+
+#[aoc(day3, part2)]
+pub fn part2(map: &Map) -> usize {
+    SLOPES_P2.iter().map(|&s| map.trees_hit(s)).product()
+}
+
+/// A slope as wide as the puzzle's, `size` rows high, with about a quarter trees.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let width = 31;
+    let rows: Vec<Vec<bool>> = (0..size.max(1))
+        .map(|_| (0..width).map(|_| rng.chance(0.25)).collect())
+        .collect();
+
+    let trees_hit = |(right, down): (usize, usize)| {
+        rows.iter()
+            .step_by(down)
+            .enumerate()
+            .filter(|&(i, row)| row[i * right % width])
+            .count()
+    };
+
+    let part1 = trees_hit(SLOPE_P1);
+    let part2: usize = SLOPES_P2.iter().map(|&slope| trees_hit(slope)).product();
+
+    let input = synth::lines(
+        rows.iter()
+            .map(|row| row.iter().map(|&tree| if tree { '#' } else { '.' }).collect::<String>()),
+    );
+
+    Synthetic::with_answers(input, vec![Some(part1.to_string()), Some(part2.to_string())])
+}
@@ -0,0 +1,193 @@
+//! Day 4: Passport Processing.
+
+use std::collections::HashMap;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use lazy_static::lazy_static;
+use parse_display::{Display, FromStr};
+use regex::Regex;
+
+use crate::{
+    error::Result,
+    input::Input,
+    synth::{Rng, Synthetic},
+};
+
+/// The fields every passport must contain (`cid` is optional).
+pub const FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+pub const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+lazy_static! {
+    static ref HAIR_COLOR_REGEX: Regex = Regex::new("^#[0-9a-f]{6}$").unwrap();
+    static ref PID_REGEX: Regex = Regex::new("^[0-9]{9}$").unwrap();
+}
+
+/// A passport as a map from field name to value.
+pub type Passport = HashMap<String, String>;
+
+#[derive(Display, FromStr, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Height {
+    #[display("{0}cm")]
+    Cm(u32),
+    #[display("{0}in")]
+    In(u32),
+}
+
+#[aoc_generator(day4)]
+pub fn parse(input: &str) -> Result<Vec<Passport>> {
+    Input::new(4, input)
+        .sections()
+        .map(|passport| {
+            let mut fields = Passport::new();
+
+            for line in passport.lines() {
+                for entry in line.text.split_whitespace() {
+                    let (key, value) = entry
+                        .split_once(':')
+                        .ok_or_else(|| line.error(entry, "expected \"key:value\""))?;
+
+                    fields.insert(key.to_string(), value.to_string());
+                }
+            }
+
+            Ok(fields)
+        })
+        .collect()
+}
+
+/// Checks that all required fields are present.
+pub fn has_fields(passport: &Passport) -> bool {
+    FIELDS.iter().all(|&f| passport.contains_key(f))
+}
+
+/// Checks that all required fields are present and hold valid values.
+pub fn is_valid(passport: &Passport) -> bool {
+    FIELDS.iter().all(|&f| {
+        passport
+            .get(f)
+            .map(|v| match f {
+                "byr" => num_in_range(v, 1920, 2002),
+                "iyr" => num_in_range(v, 2010, 2020),
+                "eyr" => num_in_range(v, 2020, 2030),
+                "hgt" => match v.parse::<Height>() {
+                    Ok(Height::Cm(h)) => (150..=193).contains(&h),
+                    Ok(Height::In(h)) => (59..=76).contains(&h),
+                    Err(_) => false,
+                },
+                "hcl" => HAIR_COLOR_REGEX.is_match(v),
+                "ecl" => EYE_COLORS.contains(&v.as_str()),
+                "pid" => PID_REGEX.is_match(v),
+                _ => unreachable!(),
+            })
+            .unwrap_or(false)
+    })
+}
+
+#[aoc(day4, part1)]
+pub fn part1(passports: &[Passport]) -> usize {
+    passports.iter().filter(|passport| has_fields(passport)).count()
+}
+
+#[aoc(day4, part2)]
+pub fn part2(passports: &[Passport]) -> usize {
+    passports.iter().filter(|passport| is_valid(passport)).count()
+}
+
+fn num_in_range(value: &str, low: usize, high: usize) -> bool {
+    value.len() == 4
+        && value
+            .parse::<usize>()
+            .map(|num| (low..=high).contains(&num))
+            .unwrap_or(false)
+}
+
+/// A valid value for `field`.
+fn valid_value(rng: &mut Rng, field: &str) -> String {
+    match field {
+        "byr" => rng.range(1920..=2002).to_string(),
+        "iyr" => rng.range(2010..=2020).to_string(),
+        "eyr" => rng.range(2020..=2030).to_string(),
+        "hgt" if rng.chance(0.5) => Height::Cm(rng.range(150..=193) as u32).to_string(),
+        "hgt" => Height::In(rng.range(59..=76) as u32).to_string(),
+        "hcl" => format!("#{:06x}", rng.below(1 << 24)),
+        "ecl" => rng.choose(&EYE_COLORS).to_string(),
+        "pid" => format!("{:09}", rng.below(1_000_000_000)),
+        "cid" => rng.range(100..=350).to_string(),
+        _ => unreachable!(),
+    }
+}
+
+/// A value for `field` which breaks exactly one of its rules.
+fn invalid_value(rng: &mut Rng, field: &str) -> String {
+    match (field, rng.below(2)) {
+        ("byr", 0) => rng.range(1900..=1919).to_string(),
+        ("byr", _) => rng.range(2003..=2030).to_string(),
+        ("iyr", 0) => rng.range(1990..=2009).to_string(),
+        ("iyr", _) => rng.range(2021..=2030).to_string(),
+        ("eyr", 0) => rng.range(2000..=2019).to_string(),
+        ("eyr", _) => rng.range(2031..=2099).to_string(),
+        ("hgt", 0) => Height::Cm(rng.range(59..=149) as u32).to_string(),
+        ("hgt", _) => rng.range(59..=193).to_string(),
+        ("hcl", 0) => format!("{:06x}", rng.below(1 << 24)),
+        ("hcl", _) => format!("#{:05x}z", rng.below(1 << 20)),
+        ("ecl", 0) => "xry".to_string(),
+        ("ecl", _) => format!("#{:06x}", rng.below(1 << 24)),
+        ("pid", 0) => format!("{:010}", rng.below(10_000_000_000)),
+        ("pid", _) => format!("{:08}", rng.below(100_000_000)),
+        _ => unreachable!(),
+    }
+}
+
+/// Passports which are either missing a required field, hold one invalid value,
+/// or are fully valid, written in a random field order over one to three lines.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let (mut part1, mut part2) = (0, 0);
+
+    let passports: Vec<String> = (0..size)
+        .map(|_| {
+            let mut fields: Vec<&str> = FIELDS.to_vec();
+
+            if rng.chance(0.5) {
+                fields.push("cid");
+            }
+
+            let missing = rng.chance(0.25);
+            let invalid = !missing && rng.chance(0.4);
+
+            if missing {
+                fields.remove(rng.index(FIELDS.len()));
+            } else {
+                part1 += 1;
+                part2 += !invalid as usize;
+            }
+
+            rng.shuffle(&mut fields);
+
+            let invalid = if invalid { Some(*rng.choose(&FIELDS)) } else { None };
+
+            let entries: Vec<String> = fields
+                .iter()
+                .map(|&field| {
+                    let value = if Some(field) == invalid {
+                        invalid_value(rng, field)
+                    } else {
+                        valid_value(rng, field)
+                    };
+
+                    format!("{}:{}", field, value)
+                })
+                .collect();
+
+            let lines = rng.range(1..=3) as usize;
+            let per_line = entries.len().div_ceil(lines);
+
+            entries
+                .chunks(per_line)
+                .map(|line| line.join(" "))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect();
+
+    Synthetic::with_answers(passports.join("\n\n"), vec![Some(part1.to_string()), Some(part2.to_string())])
+}
@@ -0,0 +1,112 @@
+//! Day 5: Binary Boarding.
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::{
+    error::Result,
+    input::Input,
+    synth::{self, Rng, Synthetic},
+};
+
+#[aoc_generator(day5)]
+pub fn parse(input: &str) -> Result<Vec<u16>> {
+    Input::new(5, input)
+        .lines()
+        .map(|line| {
+            if line.text.chars().count() != 10 {
+                return Err(line.error(line.text, "expected 10 characters"));
+            }
+
+            let bits = line.map_chars("one of 'F', 'B', 'L' or 'R'", char_value)?;
+
+            Ok(bits.into_iter().fold(0, |id, bit| id << 1 | bit))
+        })
+        .collect()
+}
+
+fn char_value(c: char) -> Option<u16> {
+    match c {
+        'F' | 'L' => Some(0),
+        'B' | 'R' => Some(1),
+        _ => None,
+    }
+}
+
+/// XOR of every integer in `0..=n`.
+pub fn xor_up_to(n: u16) -> u16 {
+    match n & 0b11 {
+        0b00 => n,
+        0b01 => 1,
+        0b10 => n | 1,
+        0b11 => 0,
+        _ => unreachable!(),
+    }
+}
+
+/// XOR of every integer in `low..=high`.
+pub fn xor_between(low: u16, high: u16) -> u16 {
+    low.checked_sub(1).map_or(0, xor_up_to) ^ xor_up_to(high)
+}
+
+#[aoc(day5, part1)]
+pub fn part1(seats: &[u16]) -> u16 {
+    seats.iter().copied().max().unwrap()
+}
+
+#[aoc(day5, part2)]
+pub fn part2(seats: &[u16]) -> u16 {
+    let (min, max, xor) = seats.iter().fold((u16::MAX, 0, 0), |(min, max, xor), &id| {
+        (min.min(id), max.max(id), xor ^ id)
+    });
+
+    xor_between(min, max) ^ xor
+}
+
+/// [`part2`] without the XOR trick: sorts the seats and looks for the gap.
+pub fn part2_reference(seats: &[u16]) -> u16 {
+    let mut seats = seats.to_vec();
+    seats.sort_unstable();
+
+    seats
+        .windows(2)
+        .find(|pair| pair[1] - pair[0] == 2)
+        .map(|pair| pair[0] + 1)
+        .unwrap()
+}
+
+/// A full flight, taking a contiguous run of seat IDs with one missing from the middle.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let len = size.clamp(3, 1000) as i64;
+    let low = rng.range(0..=1023 - len);
+    let high = low + len;
+    let missing = rng.range(low + 1..=high - 1);
+
+    let mut seats: Vec<i64> = (low..=high).filter(|&id| id != missing).collect();
+    rng.shuffle(&mut seats);
+
+    let input = synth::lines(seats.iter().map(|&id| {
+        (0..10)
+            .rev()
+            .map(|bit| match (bit >= 3, id >> bit & 1 == 1) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            })
+            .collect::<String>()
+    }));
+
+    Synthetic::with_answers(input, vec![Some(high.to_string()), Some(missing.to_string())])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_first_seat() {
+        assert_eq!(xor_between(0, 3), 0);
+        assert_eq!(part2(&[0, 2]), 1);
+        assert_eq!(part2_reference(&[0, 2]), 1);
+    }
+}
@@ -0,0 +1,100 @@
+//! Day 6: Custom Customs.
+
+use std::ops::{BitAnd, BitOr};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+
+use crate::{
+    error::Result,
+    input::Input,
+    synth::{Rng, Synthetic},
+};
+
+/// A group's answers, one bitset of questions (bit 0 is `a`) per person.
+pub type Group = Vec<u32>;
+
+#[aoc_generator(day6)]
+pub fn parse(input: &str) -> Result<Vec<Group>> {
+    Input::new(6, input)
+        .sections()
+        .map(|group| {
+            group
+                .lines()
+                .map(|line| {
+                    let answers = line.map_chars("a letter from 'a' to 'z'", char_value)?;
+
+                    Ok(answers.into_iter().fold(0, |acc, answer| acc | answer))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn char_value(c: char) -> Option<u32> {
+    if c.is_ascii_lowercase() {
+        Some(1 << (c as u8 - b'a'))
+    } else {
+        None
+    }
+}
+
+/// Combines each group's answers with `rule` and sums the questions counted.
+pub fn sum_by_rule(groups: &[Group], rule: fn(u32, u32) -> u32) -> u32 {
+    groups
+        .iter()
+        .map(|group| group.iter().copied().fold1(rule).unwrap().count_ones())
+        .sum()
+}
+
+#[aoc(day6, part1)]
+pub fn part1(groups: &[Group]) -> u32 {
+    sum_by_rule(groups, BitOr::bitor)
+}
+
+#[aoc(day6, part2)]
+pub fn part2(groups: &[Group]) -> u32 {
+    sum_by_rule(groups, BitAnd::bitand)
+}
+
+/// Groups of one to five people, each answering yes to a random set of questions.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let groups: Vec<Group> = (0..size.max(1))
+        .map(|_| {
+            (0..rng.range(1..=5))
+                .map(|_| {
+                    let first = 1 << rng.below(26);
+                    (0..26).filter(|_| rng.chance(0.4)).fold(first, |acc, bit| acc | 1 << bit)
+                })
+                .collect()
+        })
+        .collect();
+
+    let count = |rule: fn(u32, u32) -> u32| -> u32 {
+        groups
+            .iter()
+            .map(|group| group[1..].iter().fold(group[0], |acc, &person| rule(acc, person)).count_ones())
+            .sum()
+    };
+
+    let input = groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .map(|&person| {
+                    (0..26u8)
+                        .filter(|bit| person >> bit & 1 == 1)
+                        .map(|bit| (b'a' + bit) as char)
+                        .collect()
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+        .join("\n\n");
+
+    Synthetic::with_answers(
+        input,
+        vec![Some(count(BitOr::bitor).to_string()), Some(count(BitAnd::bitand).to_string())],
+    )
+}
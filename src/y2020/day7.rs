@@ -0,0 +1,149 @@
+//! Day 7: Handy Haversacks.
+
+use std::collections::{HashMap, HashSet};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    error::Result,
+    input::Input,
+    synth::{self, Rng, Synthetic},
+};
+
+/// Maps each bag color to the colors and counts of the bags it must contain.
+pub type Rules = HashMap<String, HashMap<String, usize>>;
+
+pub const MY_BAG: &str = "shiny gold";
+
+lazy_static! {
+    static ref PARENT_REGEX: Regex = Regex::new(r"^(.*?) bags contain").unwrap();
+    static ref CHILDREN_REGEX: Regex = Regex::new(r"(\d+) (.*?) bag").unwrap();
+}
+
+#[aoc_generator(day7)]
+pub fn parse(input: &str) -> Result<Rules> {
+    Input::new(7, input)
+        .lines()
+        .map(|line| {
+            let parent = PARENT_REGEX
+                .captures(line.text)
+                .ok_or_else(|| line.error(line.text, "expected \"<color> bags contain\""))?[1]
+                .to_string();
+
+            let children = CHILDREN_REGEX
+                .captures_iter(line.text)
+                .map(|c| Ok((c[2].to_string(), line.parse_token(&c[1])?)))
+                .collect::<Result<_>>()?;
+
+            Ok((parent, children))
+        })
+        .collect()
+}
+
+/// Collects every bag color which may eventually contain `bag` into `seen`.
+pub fn may_contain<'a>(rules: &'a Rules, bag: &str, seen: &mut HashSet<&'a str>) {
+    for (parent, children) in rules {
+        if children.contains_key(bag) && seen.insert(parent) {
+            may_contain(rules, parent, seen)
+        }
+    }
+}
+
+#[aoc(day7, part1)]
+pub fn part1(rules: &Rules) -> usize {
+    let mut seen = HashSet::new();
+
+    may_contain(rules, MY_BAG, &mut seen);
+
+    seen.len()
+}
+
+/// Counts `bag` itself plus every bag nested inside it.
+pub fn sum_bags(rules: &Rules, bag: &str) -> usize {
+    rules[bag]
+        .iter()
+        .map(|(color, num)| num * sum_bags(rules, color))
+        .sum::<usize>()
+        + 1
+}
+
+#[aoc(day7, part2)]
+pub fn part2(rules: &Rules) -> usize {
+    sum_bags(rules, MY_BAG) - 1
+}
+
+/// About `size` bag rules in a few layers, each bag only containing bags of the
+/// next layer so that the nesting stays shallow. My bag sits in the second layer.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    const LAYERS: usize = 6;
+
+    let count = size.max(2 * LAYERS);
+    let mut colors = HashSet::new();
+    colors.insert(MY_BAG.to_string());
+
+    while colors.len() < count {
+        let len = rng.range(3..=6) as usize;
+        let color = format!("{} {}", rng.word(len), rng.word(len));
+        colors.insert(color);
+    }
+
+    let mut colors: Vec<String> = colors.into_iter().filter(|color| color != MY_BAG).collect();
+    colors.sort();
+    rng.shuffle(&mut colors);
+
+    let layer = |i: usize| i * LAYERS / count;
+    let mine = rng.range((count / LAYERS) as i64..=(2 * count / LAYERS - 1) as i64) as usize;
+    colors.insert(mine, MY_BAG.to_string());
+
+    let children: Vec<Vec<(usize, usize)>> = (0..count)
+        .map(|i| {
+            let next: Vec<usize> = (i + 1..count).filter(|&j| layer(j) == layer(i) + 1).collect();
+
+            if next.is_empty() || rng.chance(0.15) {
+                return Vec::new();
+            }
+
+            let mut picked: Vec<usize> = (0..rng.range(1..=3)).map(|_| *rng.choose(&next)).collect();
+            picked.sort_unstable();
+            picked.dedup();
+            picked.into_iter().map(|j| (j, rng.range(1..=4) as usize)).collect()
+        })
+        .collect();
+
+    let mut contains_mine = vec![false; count];
+    let mut bags_inside = vec![0; count];
+
+    for i in (0..count).rev() {
+        contains_mine[i] = children[i].iter().any(|&(j, _)| j == mine || contains_mine[j]);
+        bags_inside[i] = children[i].iter().map(|&(j, n)| n * (bags_inside[j] + 1)).sum();
+    }
+
+    let mut lines: Vec<String> = children
+        .iter()
+        .enumerate()
+        .map(|(i, children)| {
+            let contents = if children.is_empty() {
+                "no other bags".to_string()
+            } else {
+                children
+                    .iter()
+                    .map(|&(j, n)| format!("{} {} bag{}", n, colors[j], if n == 1 { "" } else { "s" }))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+            format!("{} bags contain {}.", colors[i], contents)
+        })
+        .collect();
+
+    rng.shuffle(&mut lines);
+
+    let part1 = contains_mine.iter().filter(|&&contains| contains).count();
+
+    Synthetic::with_answers(
+        synth::lines(lines),
+        vec![Some(part1.to_string()), Some(bags_inside[mine].to_string())],
+    )
+}
@@ -0,0 +1,220 @@
+//! Day 8: Handheld Halting.
+
+use std::collections::HashSet;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use parse_display::{Display, FromStr};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Result,
+    explain,
+    input::Input,
+    synth::{self, Rng, Synthetic},
+};
+
+#[derive(Display, FromStr, Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[display("{} {0}", style = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Instruction {
+    Acc(i32),
+    Jmp(i32),
+    Nop(i32),
+}
+
+/// Why a [`Computer`] stopped running.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExitCode {
+    /// The program counter moved just past the last instruction.
+    Terminated,
+    /// An instruction was about to be executed a second time.
+    InfiniteLoop,
+}
+
+/// The handheld game console, which halts on the first repeated instruction.
+#[derive(Default)]
+pub struct Computer<'a> {
+    program: &'a [Instruction],
+    seen: HashSet<usize>,
+    pc: usize,
+    acc: i32
+}
+
+impl<'a> Computer<'a> {
+    pub fn new(program: &'a [Instruction]) -> Self {
+        Self {
+            program,
+            ..Default::default()
+        }
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn acc(&self) -> i32 {
+        self.acc
+    }
+
+    pub fn run(&mut self) -> ExitCode {
+        loop {
+            if let Err(code) = self.step() {
+                return code;
+            }
+        }
+    }
+
+    /// Executes the instruction at the program counter.
+    pub fn step(&mut self) -> Result<(), ExitCode> {
+        self.execute(self.program[self.pc])
+    }
+
+    /// Executes `instruction` in place of the one at the program counter.
+    pub fn execute(&mut self, instruction: Instruction) -> Result<(), ExitCode> {
+        if !self.seen.insert(self.pc) {
+            return Err(ExitCode::InfiniteLoop);
+        }
+
+        match instruction {
+            Instruction::Acc(i) => {
+                self.acc += i;
+                self.pc += 1;
+            },
+            Instruction::Jmp(i) => {
+                self.pc = (self.pc as i32 + i) as usize;
+            },
+            Instruction::Nop(_) => {
+                self.pc += 1;
+            }
+        }
+
+        if self.pc == self.program.len() {
+            return Err(ExitCode::Terminated);
+        }
+
+        Ok(())
+    }
+}
+
+#[aoc_generator(day8)]
+pub fn parse(input: &str) -> Result<Vec<Instruction>> {
+    Input::new(8, input)
+        .lines()
+        .map(|line| line.parse_format("acc|jmp|nop {offset}"))
+        .collect()
+}
+
+#[aoc(day8, part1)]
+pub fn part1(program: &[Instruction]) -> i32 {
+    let mut computer = Computer::new(program);
+
+    computer.run();
+
+    computer.acc
+}
+
+#[aoc(day8, part2)]
+pub fn part2(program: &[Instruction]) -> i32 {
+    for (i, &instruction) in program.iter().enumerate() {
+        let flipped = match instruction {
+            Instruction::Jmp(n) => Instruction::Nop(n),
+            Instruction::Nop(n) => Instruction::Jmp(n),
+            _ => continue
+        };
+
+        let mut computer = Computer::new(program);
+
+        loop {
+            let result = if computer.pc == i {
+                computer.execute(flipped)
+            } else {
+                computer.step()
+            };
+
+            match result {
+                Err(ExitCode::Terminated) => {
+                    explain::note(8, "flipped", || format!("line {}: {} to {}", i + 1, instruction, flipped));
+                    return computer.acc;
+                }
+                Err(ExitCode::InfiniteLoop) => break,
+                Ok(()) => (),
+            }
+        }
+    }
+
+    panic!("no solution");
+}
+
+/// A program of about `size` instructions which runs forward to its end, except
+/// for one `jmp` back to an instruction it already executed.
+///
+/// Every other `nop` points back at an executed instruction (or at itself), and
+/// every instruction skipped over by a `jmp` jumps back, so that repairing any
+/// other instruction still ends in a loop.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let len = size.max(10);
+    let bug = rng.range((len / 4) as i64..=(len * 3 / 4) as i64) as usize;
+
+    let mut program = Vec::with_capacity(len);
+    let mut executed: Vec<usize> = Vec::new();
+    let (mut acc_before_bug, mut acc_total) = (0, 0);
+
+    let back = |rng: &mut Rng, executed: &[usize], pc: usize| {
+        if executed.is_empty() || rng.chance(0.2) {
+            0
+        } else {
+            *rng.choose(executed) as i32 - pc as i32
+        }
+    };
+
+    while program.len() < len {
+        let pc = program.len();
+
+        if pc == bug {
+            let offset = back(rng, &executed, pc).min(-1);
+            program.push(Instruction::Jmp(offset));
+            executed.push(pc);
+            continue;
+        }
+
+        let limit = if pc < bug { bug } else { len };
+
+        match rng.below(10) {
+            0..=4 => {
+                let n = rng.range(-50..=50) as i32;
+
+                if pc < bug {
+                    acc_before_bug += n;
+                }
+
+                acc_total += n;
+                program.push(Instruction::Acc(n));
+                executed.push(pc);
+            }
+            5..=6 => {
+                let offset = back(rng, &executed, pc);
+                program.push(Instruction::Nop(offset));
+                executed.push(pc);
+            }
+            _ => {
+                let skip = rng.range(1..=4).min((limit - pc - 1) as i64) as usize;
+
+                program.push(Instruction::Jmp(skip as i32 + 1));
+                executed.push(pc);
+
+                for skipped in pc + 1..=pc + skip {
+                    let offset = back(rng, &executed, skipped).min(-1);
+                    program.push(Instruction::Jmp(offset));
+                }
+            }
+        }
+    }
+
+    let input = synth::lines(program.iter().map(|instruction| match instruction {
+        Instruction::Acc(n) => format!("acc {:+}", n),
+        Instruction::Jmp(n) => format!("jmp {:+}", n),
+        Instruction::Nop(n) => format!("nop {:+}", n),
+    }));
+
+    Synthetic::with_answers(input, vec![Some(acc_before_bug.to_string()), Some(acc_total.to_string())])
+}
@@ -0,0 +1,147 @@
+//! Day 9: Encoding Error.
+
+use std::collections::HashSet;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+
+use crate::{
+    error::{Error, Result},
+    input::Input,
+    limits::Checkpoint,
+    synth::{self, Rng, Synthetic},
+};
+
+/// The length of the XMAS preamble.
+pub const WINDOW_SIZE: usize = 25;
+
+#[aoc_generator(day9)]
+pub fn parse(input: &str) -> Result<Vec<u64>> {
+    Input::new(9, input).lines().map(|line| line.parse()).collect()
+}
+
+/// Checks whether two entries of `nums` add up to `target`.
+pub fn contains_2sum(nums: &[u64], target: u64) -> bool {
+    let mut seen = HashSet::new();
+
+    for &num in nums {
+        if num > target {
+            continue;
+        }
+
+        let complement = target - num;
+        if seen.contains(&complement) {
+            return true;
+        }
+
+        seen.insert(num);
+    }
+
+    false
+}
+
+#[aoc(day9, part1)]
+pub fn part1(nums: &[u64]) -> Result<u64> {
+    nums.windows(WINDOW_SIZE + 1)
+        .find(|window| !contains_2sum(&window[..WINDOW_SIZE], window[WINDOW_SIZE]))
+        .map(|window| window[WINDOW_SIZE])
+        .ok_or_else(|| Error::gave_up(9, "no number breaks the XMAS rule"))
+}
+
+#[aoc(day9, part2)]
+pub fn part2(nums: &[u64]) -> Result<u64> {
+    let target = part1(nums)?;
+    let mut checkpoint = Checkpoint::new(9);
+
+    let mut low = 0;
+    let mut high = 0;
+    let mut sum = 0;
+
+    loop {
+        if sum == target && high - low >= 2 {
+            let (min, max) = nums[low..high].iter().minmax().into_option().unwrap();
+            return Ok(min + max);
+        } else if sum <= target {
+            if high == nums.len() {
+                return Err(checkpoint.give_up(format!("no run of numbers sums to {}", target)));
+            }
+
+            sum += nums[high];
+            high += 1;
+        } else {
+            sum -= nums[low];
+            low += 1;
+        }
+
+        checkpoint.tick()?;
+    }
+}
+
+/// About `size` numbers, each the sum of two of the smallest numbers before it so
+/// that they grow slowly, except for one which is instead the sum of an earlier run.
+pub fn synthesize(rng: &mut Rng, size: usize) -> Synthetic {
+    let len = size.clamp(WINDOW_SIZE + 10, 1000);
+    let invalid_at = rng.range((WINDOW_SIZE + 5) as i64..=(len - 1) as i64) as usize;
+
+    let mut nums: Vec<u64> = (1..=3 * WINDOW_SIZE as u64).collect();
+    rng.shuffle(&mut nums);
+    nums.truncate(WINDOW_SIZE);
+
+    while nums.len() < len {
+        let window = &nums[nums.len() - WINDOW_SIZE..];
+
+        if nums.len() == invalid_at {
+            let start = rng.index(nums.len() - 3);
+            let end = (start + rng.range(3..=17) as usize).min(nums.len());
+            let sum = nums[start..end].iter().sum();
+
+            if !contains_2sum(window, sum) && !nums.contains(&sum) {
+                nums.push(sum);
+            }
+
+            continue;
+        }
+
+        let mut smallest = window.to_vec();
+        smallest.sort_unstable();
+        smallest.truncate(8);
+
+        let a = rng.index(smallest.len());
+        let b = (a + 1 + rng.index(smallest.len() - 1)) % smallest.len();
+        nums.push(smallest[a] + smallest[b]);
+    }
+
+    let invalid = nums[invalid_at];
+    let weakness = (0..invalid_at)
+        .find_map(|low| {
+            let mut sum = 0;
+
+            nums[low..invalid_at].iter().enumerate().find_map(|(i, &num)| {
+                sum += num;
+
+                if sum == invalid {
+                    let (min, max) = nums[low..=low + i].iter().minmax().into_option().unwrap();
+                    Some(min + max)
+                } else {
+                    None
+                }
+            })
+        })
+        .unwrap();
+
+    Synthetic::with_answers(synth::lines(nums), vec![Some(invalid.to_string()), Some(weakness.to_string())])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_no_invalid_number() {
+        assert!(part1(&[1, 2, 3]).is_err());
+        assert!(part2(&[1, 2, 3]).is_err());
+
+        let valid: Vec<u64> = (1..=WINDOW_SIZE as u64).chain(vec![3, 40, 49]).collect();
+        assert!(part1(&valid).is_err());
+    }
+}